@@ -8,5 +8,7 @@ pub mod service;
 pub mod store;
 pub mod store_file;
 pub mod store_memory;
+pub mod store_postgres;
+pub mod store_sled;
 pub mod store_sqlite;
 pub mod types;
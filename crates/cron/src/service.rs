@@ -0,0 +1,444 @@
+//! Runtime cron service: owns a [`CronStore`] and drives job scheduling.
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use anyhow::{Context, Result};
+use tokio::sync::Mutex;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use crate::{
+    store::CronStore,
+    types::{CronJob, CronJobCreate, CronJobPatch, CronPayload, CronRunRecord, RunRetention, RunStatus},
+};
+
+/// Store-level fallback for [`RunRetention::max_runs_retained`] when a job
+/// doesn't override it.
+pub const DEFAULT_MAX_RUNS_RETAINED: u32 = 100;
+
+/// Per-request timeout for [`CronService::run_webhook`] deliveries. Without
+/// this a hung or black-holed endpoint would block the first attempt
+/// forever, never reaching the retry/backoff loop, and keep the job claimed
+/// (the heartbeat renews its lease for as long as the task runs).
+const WEBHOOK_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Summary returned by [`CronService::status`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CronStatus {
+    pub job_count: usize,
+    pub worker_id: String,
+}
+
+/// Drives scheduled jobs against a pluggable [`CronStore`].
+pub struct CronService {
+    store: Arc<dyn CronStore>,
+    worker_id: String,
+    lease_ttl_ms: u64,
+    /// Claim token for each job this worker currently holds a lease on, so
+    /// [`Self::mark_done`] and the heartbeat spawned in [`Self::claim_and_run`]
+    /// can fence their `renew_lease`/`release_claim` calls against a claim
+    /// that's since been released and re-claimed by someone else.
+    claims: Mutex<HashMap<String, String>>,
+}
+
+impl CronService {
+    /// Create a service backed by `store`, identifying this process's claims
+    /// with a freshly generated worker id.
+    pub fn new(store: Arc<dyn CronStore>) -> Self {
+        Self {
+            store,
+            worker_id: Uuid::new_v4().to_string(),
+            lease_ttl_ms: 60_000,
+            claims: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn list(&self) -> Vec<CronJob> {
+        self.store.load_jobs().await.unwrap_or_default()
+    }
+
+    pub async fn status(&self) -> CronStatus {
+        CronStatus {
+            job_count: self.list().await.len(),
+            worker_id: self.worker_id.clone(),
+        }
+    }
+
+    pub async fn add(&self, _create: CronJobCreate) -> Result<CronJob> {
+        anyhow::bail!("not implemented in this snapshot")
+    }
+
+    pub async fn update(&self, _id: &str, _patch: CronJobPatch) -> Result<CronJob> {
+        anyhow::bail!("not implemented in this snapshot")
+    }
+
+    pub async fn remove(&self, id: &str) -> Result<()> {
+        self.store.delete_job(id).await
+    }
+
+    /// Run a job's payload immediately, out of band from the scheduler.
+    /// `force` is accepted for API symmetry with a future "skip if already
+    /// claimed" check, but isn't consulted yet.
+    pub async fn run(&self, id: &str, _force: bool) -> Result<()> {
+        let job = self
+            .list()
+            .await
+            .into_iter()
+            .find(|j| j.id == id)
+            .ok_or_else(|| anyhow::anyhow!("job not found: {id}"))?;
+
+        match &job.payload {
+            CronPayload::Webhook { .. } => self.run_webhook(&job).await,
+            CronPayload::SystemEvent { .. } => {
+                anyhow::bail!("not implemented in this snapshot")
+            }
+        }
+    }
+
+    /// Deliver a [`CronPayload::Webhook`], retrying per the job's
+    /// [`crate::types::RetryPolicy`] and recording one [`CronRunRecord`] per
+    /// attempt so [`Self::runs`] shows the full delivery sequence. The run is
+    /// only surfaced as an overall failure once the final attempt fails.
+    async fn run_webhook(&self, job: &CronJob) -> Result<()> {
+        let CronPayload::Webhook {
+            url,
+            method,
+            headers,
+            body,
+        } = &job.payload
+        else {
+            unreachable!("caller matched on CronPayload::Webhook");
+        };
+
+        let client = reqwest::Client::builder()
+            .timeout(WEBHOOK_REQUEST_TIMEOUT)
+            .build()
+            .context("failed to build webhook HTTP client")?;
+        let http_method = reqwest::Method::from_bytes(method.as_bytes())
+            .map_err(|e| anyhow::anyhow!("invalid webhook method {method:?}: {e}"))?;
+
+        let mut last_err: Option<String> = None;
+        for attempt in 1..=job.retry.max_attempts.max(1) {
+            let started_at_ms = now_ms();
+            let mut request = client.request(http_method.clone(), url);
+            for (name, value) in headers {
+                request = request.header(name.as_str(), value.as_str());
+            }
+            if let Some(body) = body {
+                request = request.body(body.clone());
+            }
+
+            let outcome = request.send().await;
+            let finished_at_ms = now_ms();
+            let is_last_attempt = attempt == job.retry.max_attempts.max(1);
+
+            let (status, error, output) = match outcome {
+                Ok(response) => {
+                    let code = response.status();
+                    let text = response.text().await.unwrap_or_default();
+                    if code.is_success() {
+                        (RunStatus::Ok, None, Some(text))
+                    } else {
+                        (RunStatus::Error, Some(format!("webhook returned {code}")), Some(text))
+                    }
+                }
+                Err(e) => (RunStatus::Error, Some(e.to_string()), None),
+            };
+
+            let ok = status == RunStatus::Ok;
+            if !ok {
+                last_err = error.clone();
+            }
+
+            self.store
+                .append_run(
+                    &job.id,
+                    &CronRunRecord {
+                        job_id: job.id.clone(),
+                        started_at_ms,
+                        finished_at_ms,
+                        status,
+                        error,
+                        duration_ms: finished_at_ms.saturating_sub(started_at_ms),
+                        output,
+                        attempt,
+                    },
+                )
+                .await?;
+            self.prune_runs(&job.id, job.retention).await?;
+
+            if ok {
+                self.mark_done(&job.id).await?;
+                return Ok(());
+            }
+            if !is_last_attempt {
+                tokio::time::sleep(Duration::from_millis(job.retry.backoff_ms)).await;
+            }
+        }
+
+        self.mark_done(&job.id).await?;
+        anyhow::bail!(
+            "webhook delivery failed after {} attempt(s): {}",
+            job.retry.max_attempts.max(1),
+            last_err.unwrap_or_default()
+        )
+    }
+
+    pub async fn runs(
+        &self,
+        id: &str,
+        limit: usize,
+        status_filter: Option<RunStatus>,
+    ) -> Result<Vec<CronRunRecord>> {
+        self.store.get_runs(id, limit, status_filter).await
+    }
+
+    /// Count of runs for `id` grouped by status, for alerting on consecutive failures.
+    pub async fn run_status_counts(&self, id: &str) -> Result<Vec<(RunStatus, u64)>> {
+        self.store.count_runs_by_status(id).await
+    }
+
+    /// Prune a job's run history down to its configured retention, falling
+    /// back to [`DEFAULT_MAX_RUNS_RETAINED`] when the job has no override.
+    /// Called by [`Self::run_webhook`] after each attempt is recorded.
+    pub async fn prune_runs(&self, job_id: &str, retention: RunRetention) -> Result<()> {
+        let keep = retention.max_runs_retained.unwrap_or(DEFAULT_MAX_RUNS_RETAINED) as usize;
+        self.store.prune_runs(job_id, keep, retention.max_age_ms, now_ms()).await
+    }
+
+    /// Claim up to `limit` due jobs for this worker and spawn a heartbeat task
+    /// per claimed job that renews its lease every `lease_ttl_ms / 3` until
+    /// `mark_done` is called for it.
+    pub async fn claim_and_run(&self, limit: usize) -> Result<Vec<CronJob>> {
+        let now_ms = now_ms();
+        let claimed = self
+            .store
+            .claim_due_jobs(&self.worker_id, now_ms, self.lease_ttl_ms, limit)
+            .await?;
+
+        let mut jobs = Vec::with_capacity(claimed.len());
+        for (job, claim_token) in claimed {
+            self.claims.lock().await.insert(job.id.clone(), claim_token.clone());
+            self.spawn_heartbeat(job.id.clone(), claim_token);
+            jobs.push(job);
+        }
+        Ok(jobs)
+    }
+
+    /// Release a job's claim after its run finishes, stopping the heartbeat.
+    /// A no-op if this worker doesn't currently hold a claim token for
+    /// `job_id` (e.g. `mark_done` called twice for the same job).
+    pub async fn mark_done(&self, job_id: &str) -> Result<()> {
+        let claim_token = self.claims.lock().await.remove(job_id);
+        let Some(claim_token) = claim_token else {
+            return Ok(());
+        };
+        self.store.release_claim(job_id, &self.worker_id, &claim_token).await
+    }
+
+    fn spawn_heartbeat(&self, job_id: String, claim_token: String) {
+        let store = Arc::clone(&self.store);
+        let worker_id = self.worker_id.clone();
+        let lease_ttl_ms = self.lease_ttl_ms;
+        let interval = Duration::from_millis((lease_ttl_ms / 3).max(1000));
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let renewed = store
+                    .renew_lease(&job_id, &worker_id, &claim_token, now_ms(), lease_ttl_ms)
+                    .await;
+                match renewed {
+                    Ok(()) => continue,
+                    Err(e) => {
+                        // Lease already released (run finished) or lost to another
+                        // worker; either way, stop heartbeating.
+                        warn!(job_id, error = %e, "stopping lease heartbeat");
+                        break;
+                    }
+                }
+            }
+        });
+    }
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_else(|e| {
+            error!(error = %e, "system clock before epoch");
+            0
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+    use crate::{
+        store_sqlite::SqliteStore,
+        types::{CronJobState, CronSchedule, RetryPolicy, SessionTarget},
+    };
+
+    /// Spawns a one-shot mock HTTP server that replies to successive
+    /// connections with `responses` in order, so `run_webhook`'s retry loop
+    /// can be driven against real socket I/O (the cron crate has no
+    /// `Prober`-style trait seam to fake the HTTP client through).
+    async fn spawn_mock_webhook(responses: Vec<(u16, &'static str)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf).await;
+                let reason = if (200..300).contains(&status) { "OK" } else { "Internal Server Error" };
+                let response = format!(
+                    "HTTP/1.1 {status} {reason}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{addr}/webhook")
+    }
+
+    fn webhook_job(id: &str, url: String, max_attempts: u32, max_runs_retained: u32) -> CronJob {
+        CronJob {
+            id: id.to_string(),
+            name: "test job".into(),
+            enabled: true,
+            delete_after_run: false,
+            schedule: CronSchedule::At { at_ms: 0 },
+            payload: CronPayload::Webhook {
+                url,
+                method: "POST".into(),
+                headers: HashMap::new(),
+                body: None,
+            },
+            session_target: SessionTarget::Main,
+            state: CronJobState::default(),
+            retention: RunRetention { max_runs_retained: Some(max_runs_retained), max_age_ms: None },
+            retry: RetryPolicy { max_attempts, backoff_ms: 5 },
+            created_at_ms: 0,
+            updated_at_ms: 0,
+        }
+    }
+
+    /// Delegates every call to an inner [`SqliteStore`], recording the order
+    /// `append_run`/`prune_runs` are invoked in so a test can assert
+    /// `run_webhook` prunes after every attempt, not just the last one.
+    struct SpyStore {
+        inner: SqliteStore,
+        calls: Mutex<Vec<&'static str>>,
+    }
+
+    #[async_trait]
+    impl CronStore for SpyStore {
+        async fn load_jobs(&self) -> Result<Vec<CronJob>> {
+            self.inner.load_jobs().await
+        }
+        async fn save_job(&self, job: &CronJob) -> Result<()> {
+            self.inner.save_job(job).await
+        }
+        async fn delete_job(&self, id: &str) -> Result<()> {
+            self.inner.delete_job(id).await
+        }
+        async fn update_job(&self, job: &CronJob) -> Result<()> {
+            self.inner.update_job(job).await
+        }
+        async fn append_run(&self, job_id: &str, run: &CronRunRecord) -> Result<()> {
+            self.calls.lock().await.push("append_run");
+            self.inner.append_run(job_id, run).await
+        }
+        async fn get_runs(
+            &self,
+            job_id: &str,
+            limit: usize,
+            status_filter: Option<RunStatus>,
+        ) -> Result<Vec<CronRunRecord>> {
+            self.inner.get_runs(job_id, limit, status_filter).await
+        }
+        async fn count_runs_by_status(&self, job_id: &str) -> Result<Vec<(RunStatus, u64)>> {
+            self.inner.count_runs_by_status(job_id).await
+        }
+        async fn claim_due_jobs(
+            &self,
+            worker_id: &str,
+            now_ms: u64,
+            lease_ttl_ms: u64,
+            limit: usize,
+        ) -> Result<Vec<(CronJob, String)>> {
+            self.inner.claim_due_jobs(worker_id, now_ms, lease_ttl_ms, limit).await
+        }
+        async fn renew_lease(
+            &self,
+            job_id: &str,
+            worker_id: &str,
+            claim_token: &str,
+            now_ms: u64,
+            lease_ttl_ms: u64,
+        ) -> Result<()> {
+            self.inner.renew_lease(job_id, worker_id, claim_token, now_ms, lease_ttl_ms).await
+        }
+        async fn release_claim(&self, job_id: &str, worker_id: &str, claim_token: &str) -> Result<()> {
+            self.inner.release_claim(job_id, worker_id, claim_token).await
+        }
+        async fn prune_runs(&self, job_id: &str, keep: usize, max_age_ms: Option<u64>, now_ms: u64) -> Result<()> {
+            self.calls.lock().await.push("prune_runs");
+            self.inner.prune_runs(job_id, keep, max_age_ms, now_ms).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_webhook_retries_then_succeeds_recording_every_attempt() {
+        let url = spawn_mock_webhook(vec![(500, ""), (200, "ok")]).await;
+        let job = webhook_job("job-1", url, 2, 100);
+
+        let store = Arc::new(SpyStore {
+            inner: SqliteStore::new("sqlite::memory:").await.unwrap(),
+            calls: Mutex::new(Vec::new()),
+        });
+        store.save_job(&job).await.unwrap();
+        let service = CronService::new(store.clone());
+
+        service.run("job-1", false).await.unwrap();
+
+        let runs = store.get_runs("job-1", 10, None).await.unwrap();
+        assert_eq!(runs.len(), 2, "one CronRunRecord per attempt");
+        assert_eq!(runs[0].attempt, 1);
+        assert_eq!(runs[0].status, RunStatus::Error);
+        assert_eq!(runs[1].attempt, 2);
+        assert_eq!(runs[1].status, RunStatus::Ok);
+
+        let calls = store.calls.lock().await.clone();
+        assert_eq!(
+            calls,
+            vec!["append_run", "prune_runs", "append_run", "prune_runs"],
+            "prune_runs must fire after every append_run, not just the last"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_webhook_fails_after_exhausting_retries() {
+        let url = spawn_mock_webhook(vec![(500, ""), (500, "")]).await;
+        let job = webhook_job("job-2", url, 2, 100);
+
+        let store: Arc<dyn CronStore> = Arc::new(SqliteStore::new("sqlite::memory:").await.unwrap());
+        store.save_job(&job).await.unwrap();
+        let service = CronService::new(store.clone());
+
+        let result = service.run("job-2", false).await;
+        assert!(result.is_err(), "should fail once every attempt is exhausted");
+
+        let runs = store.get_runs("job-2", 10, None).await.unwrap();
+        assert_eq!(runs.len(), 2, "one CronRunRecord per attempt, including the last failure");
+        assert!(runs.iter().all(|r| r.status == RunStatus::Error));
+    }
+}
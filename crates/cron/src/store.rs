@@ -0,0 +1,123 @@
+//! Storage trait implemented by each cron persistence backend.
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::types::{CronJob, CronRunRecord, RunStatus};
+
+/// Persistence for cron jobs and their run history.
+///
+/// Implementations: [`crate::store_sqlite::SqliteStore`],
+/// [`crate::store_postgres::PostgresStore`], [`crate::store_sled::SledStore`].
+#[async_trait]
+pub trait CronStore: Send + Sync {
+    /// Load every job, in no particular order.
+    async fn load_jobs(&self) -> Result<Vec<CronJob>>;
+
+    /// Insert or replace a job.
+    async fn save_job(&self, job: &CronJob) -> Result<()>;
+
+    /// Remove a job. Errors if the job does not exist.
+    async fn delete_job(&self, id: &str) -> Result<()>;
+
+    /// Replace an existing job's data. Errors if the job does not exist.
+    async fn update_job(&self, job: &CronJob) -> Result<()>;
+
+    /// Append a completed run record for `job_id`.
+    async fn append_run(&self, job_id: &str, run: &CronRunRecord) -> Result<()>;
+
+    /// Fetch up to `limit` most recent runs for `job_id`, returned oldest first.
+    /// When `status_filter` is set, only runs with that status are returned
+    /// (e.g. "show me the last 10 failed runs").
+    async fn get_runs(
+        &self,
+        job_id: &str,
+        limit: usize,
+        status_filter: Option<RunStatus>,
+    ) -> Result<Vec<CronRunRecord>>;
+
+    /// Count runs for `job_id` grouped by status, for dashboards/alerting on
+    /// consecutive failures.
+    async fn count_runs_by_status(&self, job_id: &str) -> Result<Vec<(RunStatus, u64)>>;
+
+    /// Atomically claim up to `limit` due, unclaimed (or lease-expired) jobs for
+    /// `worker_id`, stamping `claimed_until_ms = now_ms + lease_ttl_ms` on the
+    /// claimed rows so no other worker can claim them until the lease expires.
+    /// Each claimed job is returned alongside a fresh, opaque claim token
+    /// unique to this particular claim (not just this `worker_id`), so
+    /// [`Self::renew_lease`]/[`Self::release_claim`] can tell a stale claim of
+    /// the same job apart from the current one even when both were made by
+    /// the same worker.
+    ///
+    /// Implementations must make the claim-and-stamp step atomic (e.g. via
+    /// `SELECT ... FOR UPDATE SKIP LOCKED` in one transaction on backends that
+    /// support it) so at most one worker ever runs a given due job per tick.
+    async fn claim_due_jobs(
+        &self,
+        worker_id: &str,
+        now_ms: u64,
+        lease_ttl_ms: u64,
+        limit: usize,
+    ) -> Result<Vec<(CronJob, String)>>;
+
+    /// Extend a previously claimed job's lease by `lease_ttl_ms` from `now_ms`.
+    /// Called periodically while a run is in flight so a slow job isn't
+    /// re-claimed by another worker mid-run. Errors (rather than silently
+    /// no-opping) if `claim_token` doesn't match the job's current claim --
+    /// e.g. a heartbeat left running for a claim that has since been
+    /// released and re-claimed.
+    async fn renew_lease(
+        &self,
+        job_id: &str,
+        worker_id: &str,
+        claim_token: &str,
+        now_ms: u64,
+        lease_ttl_ms: u64,
+    ) -> Result<()>;
+
+    /// Clear a job's claim, e.g. after a run completes (successfully or not).
+    /// A mismatched `claim_token` is a no-op: the claim being released isn't
+    /// the current one, so there's nothing for this call to clear.
+    async fn release_claim(&self, job_id: &str, worker_id: &str, claim_token: &str) -> Result<()>;
+
+    /// Delete all but the `keep` most recent runs for `job_id`, then
+    /// additionally delete any surviving run older than `max_age_ms` (from
+    /// `now_ms`) when set, bounding `cron_runs` growth for long-lived jobs.
+    async fn prune_runs(&self, job_id: &str, keep: usize, max_age_ms: Option<u64>, now_ms: u64) -> Result<()>;
+}
+
+/// Construct a [`CronStore`] backend selected by the scheme of `database_url`
+/// (`sqlite:...`, `postgres:...`/`postgresql:...`, or `sled:...`, the last
+/// taking the rest of the URL as a filesystem path for
+/// [`crate::store_sled::SledStore::open`]).
+pub async fn open_store(database_url: &str) -> Result<std::sync::Arc<dyn CronStore>> {
+    if database_url.starts_with("postgres:") || database_url.starts_with("postgresql:") {
+        let store = crate::store_postgres::PostgresStore::new(database_url).await?;
+        Ok(std::sync::Arc::new(store))
+    } else if let Some(path) = database_url.strip_prefix("sled:") {
+        let store = crate::store_sled::SledStore::open(path)?;
+        Ok(std::sync::Arc::new(store))
+    } else {
+        let store = crate::store_sqlite::SqliteStore::new(database_url).await?;
+        Ok(std::sync::Arc::new(store))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::open_store;
+
+    #[tokio::test]
+    async fn test_open_store_dispatches_sled_scheme() {
+        let dir = TempDir::new().unwrap();
+        let url = format!("sled:{}", dir.path().join("jobs.sled").display());
+        assert!(open_store(&url).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_open_store_falls_back_to_sqlite() {
+        assert!(open_store("sqlite::memory:").await.is_ok());
+    }
+}
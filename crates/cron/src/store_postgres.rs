@@ -0,0 +1,463 @@
+//! PostgreSQL-backed cron store using sqlx, for operators running multiple
+//! gateway instances against a shared database instead of a single local
+//! SQLite file.
+
+use {
+    anyhow::{Context, Result},
+    async_trait::async_trait,
+    sqlx::{PgPool, Row, postgres::PgPoolOptions},
+    uuid::Uuid,
+};
+
+use crate::{
+    store::CronStore,
+    types::{CronJob, CronRunRecord, RunStatus},
+};
+
+/// PostgreSQL-backed persistence for cron jobs and run history.
+pub struct PostgresStore {
+    pool: PgPool,
+}
+
+impl PostgresStore {
+    /// Create a new store and run migrations.
+    pub async fn new(database_url: &str) -> Result<Self> {
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(database_url)
+            .await
+            .context("failed to connect to PostgreSQL")?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS cron_jobs (
+                id TEXT PRIMARY KEY,
+                data JSONB NOT NULL,
+                next_run_ms BIGINT,
+                claimed_by TEXT,
+                claimed_until_ms BIGINT,
+                claim_token TEXT
+            )",
+        )
+        .execute(&pool)
+        .await?;
+
+        sqlx::query("ALTER TABLE cron_jobs ADD COLUMN IF NOT EXISTS next_run_ms BIGINT")
+            .execute(&pool)
+            .await?;
+        sqlx::query("ALTER TABLE cron_jobs ADD COLUMN IF NOT EXISTS claimed_by TEXT")
+            .execute(&pool)
+            .await?;
+        sqlx::query("ALTER TABLE cron_jobs ADD COLUMN IF NOT EXISTS claimed_until_ms BIGINT")
+            .execute(&pool)
+            .await?;
+        sqlx::query("ALTER TABLE cron_jobs ADD COLUMN IF NOT EXISTS claim_token TEXT")
+            .execute(&pool)
+            .await?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_cron_jobs_due ON cron_jobs(next_run_ms, claimed_until_ms)",
+        )
+        .execute(&pool)
+        .await?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS cron_runs (
+                id BIGSERIAL PRIMARY KEY,
+                job_id TEXT NOT NULL,
+                started_at_ms BIGINT NOT NULL,
+                finished_at_ms BIGINT NOT NULL,
+                status TEXT NOT NULL CHECK (status IN ('ok', 'error', 'skipped')),
+                error TEXT,
+                duration_ms BIGINT NOT NULL,
+                output TEXT,
+                attempt INTEGER,
+                FOREIGN KEY (job_id) REFERENCES cron_jobs(id)
+            )",
+        )
+        .execute(&pool)
+        .await?;
+
+        sqlx::query("ALTER TABLE cron_runs ADD COLUMN IF NOT EXISTS attempt INTEGER")
+            .execute(&pool)
+            .await?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_cron_runs_job_id ON cron_runs(job_id, started_at_ms DESC)",
+        )
+        .execute(&pool)
+        .await?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_cron_runs_job_status
+             ON cron_runs(job_id, status, started_at_ms DESC)",
+        )
+        .execute(&pool)
+        .await?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_cron_runs_job_attempt
+             ON cron_runs(job_id, started_at_ms, attempt)",
+        )
+        .execute(&pool)
+        .await?;
+
+        Ok(Self { pool })
+    }
+}
+
+#[async_trait]
+impl CronStore for PostgresStore {
+    async fn load_jobs(&self) -> Result<Vec<CronJob>> {
+        let rows = sqlx::query("SELECT data FROM cron_jobs")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut jobs = Vec::with_capacity(rows.len());
+        for row in rows {
+            let data: serde_json::Value = row.get("data");
+            let job: CronJob = serde_json::from_value(data)?;
+            jobs.push(job);
+        }
+        Ok(jobs)
+    }
+
+    async fn save_job(&self, job: &CronJob) -> Result<()> {
+        let data = serde_json::to_value(job)?;
+        let next_run_ms = job.state.next_run_ms.map(|v| v as i64);
+        sqlx::query(
+            "INSERT INTO cron_jobs (id, data, next_run_ms) VALUES ($1, $2, $3)
+             ON CONFLICT(id) DO UPDATE SET data = excluded.data, next_run_ms = excluded.next_run_ms",
+        )
+        .bind(&job.id)
+        .bind(&data)
+        .bind(next_run_ms)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_job(&self, id: &str) -> Result<()> {
+        let result = sqlx::query("DELETE FROM cron_jobs WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            anyhow::bail!("job not found: {id}");
+        }
+        Ok(())
+    }
+
+    async fn update_job(&self, job: &CronJob) -> Result<()> {
+        let data = serde_json::to_value(job)?;
+        let next_run_ms = job.state.next_run_ms.map(|v| v as i64);
+        let result = sqlx::query("UPDATE cron_jobs SET data = $1, next_run_ms = $2 WHERE id = $3")
+            .bind(&data)
+            .bind(next_run_ms)
+            .bind(&job.id)
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            anyhow::bail!("job not found: {}", job.id);
+        }
+        Ok(())
+    }
+
+    async fn append_run(&self, job_id: &str, run: &CronRunRecord) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO cron_runs (job_id, started_at_ms, finished_at_ms, status, error, duration_ms, output, attempt)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+        )
+        .bind(job_id)
+        .bind(run.started_at_ms as i64)
+        .bind(run.finished_at_ms as i64)
+        .bind(run.status.as_str())
+        .bind(&run.error)
+        .bind(run.duration_ms as i64)
+        .bind(&run.output)
+        .bind(run.attempt as i32)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_runs(
+        &self,
+        job_id: &str,
+        limit: usize,
+        status_filter: Option<RunStatus>,
+    ) -> Result<Vec<CronRunRecord>> {
+        let rows = match status_filter {
+            Some(status) => {
+                sqlx::query(
+                    "SELECT job_id, started_at_ms, finished_at_ms, status, error, duration_ms, output, attempt
+                     FROM cron_runs
+                     WHERE job_id = $1 AND status = $2
+                     ORDER BY started_at_ms DESC
+                     LIMIT $3",
+                )
+                .bind(job_id)
+                .bind(status.as_str())
+                .bind(limit as i64)
+                .fetch_all(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query(
+                    "SELECT job_id, started_at_ms, finished_at_ms, status, error, duration_ms, output, attempt
+                     FROM cron_runs
+                     WHERE job_id = $1
+                     ORDER BY started_at_ms DESC
+                     LIMIT $2",
+                )
+                .bind(job_id)
+                .bind(limit as i64)
+                .fetch_all(&self.pool)
+                .await?
+            }
+        };
+
+        let mut runs = Vec::with_capacity(rows.len());
+        for row in rows {
+            let status_str: String = row.get("status");
+            let status: RunStatus = status_str.parse()?;
+            let attempt = row.get::<Option<i32>, _>("attempt").unwrap_or(1) as u32;
+            runs.push(CronRunRecord {
+                job_id: row.get("job_id"),
+                started_at_ms: row.get::<i64, _>("started_at_ms") as u64,
+                finished_at_ms: row.get::<i64, _>("finished_at_ms") as u64,
+                status,
+                error: row.get("error"),
+                duration_ms: row.get::<i64, _>("duration_ms") as u64,
+                output: row.get("output"),
+                attempt,
+            });
+        }
+        // Reverse so oldest first (consistent with other stores).
+        runs.reverse();
+        Ok(runs)
+    }
+
+    async fn count_runs_by_status(&self, job_id: &str) -> Result<Vec<(RunStatus, u64)>> {
+        let rows = sqlx::query(
+            "SELECT status, COUNT(*) as n FROM cron_runs WHERE job_id = $1 GROUP BY status",
+        )
+        .bind(job_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut counts = Vec::with_capacity(rows.len());
+        for row in rows {
+            let status_str: String = row.get("status");
+            let status: RunStatus = status_str.parse()?;
+            let n: i64 = row.get("n");
+            counts.push((status, n as u64));
+        }
+        Ok(counts)
+    }
+
+    async fn prune_runs(&self, job_id: &str, keep: usize, max_age_ms: Option<u64>, now_ms: u64) -> Result<()> {
+        sqlx::query(
+            "DELETE FROM cron_runs WHERE job_id = $1 AND id NOT IN (
+                 SELECT id FROM cron_runs WHERE job_id = $1 ORDER BY started_at_ms DESC LIMIT $2
+             )",
+        )
+        .bind(job_id)
+        .bind(keep as i64)
+        .execute(&self.pool)
+        .await?;
+
+        if let Some(max_age_ms) = max_age_ms {
+            let cutoff = now_ms.saturating_sub(max_age_ms) as i64;
+            sqlx::query("DELETE FROM cron_runs WHERE job_id = $1 AND started_at_ms < $2")
+                .bind(job_id)
+                .bind(cutoff)
+                .execute(&self.pool)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn claim_due_jobs(
+        &self,
+        worker_id: &str,
+        now_ms: u64,
+        lease_ttl_ms: u64,
+        limit: usize,
+    ) -> Result<Vec<(CronJob, String)>> {
+        let mut tx = self.pool.begin().await?;
+        let claimed_until_ms = (now_ms + lease_ttl_ms) as i64;
+
+        let rows = sqlx::query(
+            "SELECT id, data FROM cron_jobs
+             WHERE next_run_ms IS NOT NULL AND next_run_ms <= $1
+               AND (claimed_until_ms IS NULL OR claimed_until_ms < $1)
+             ORDER BY next_run_ms
+             LIMIT $2
+             FOR UPDATE SKIP LOCKED",
+        )
+        .bind(now_ms as i64)
+        .bind(limit as i64)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // Stamp each claimed row with its own fresh claim token, rather than
+        // one token for the whole batch, so two jobs claimed in the same call
+        // don't share a fencing token.
+        let mut jobs = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let id: String = row.get("id");
+            let data: serde_json::Value = row.get("data");
+            let job: CronJob = serde_json::from_value(data)?;
+
+            let claim_token = Uuid::new_v4().to_string();
+            sqlx::query(
+                "UPDATE cron_jobs SET claimed_by = $1, claimed_until_ms = $2, claim_token = $3 WHERE id = $4",
+            )
+            .bind(worker_id)
+            .bind(claimed_until_ms)
+            .bind(&claim_token)
+            .bind(&id)
+            .execute(&mut *tx)
+            .await?;
+
+            jobs.push((job, claim_token));
+        }
+
+        tx.commit().await?;
+        Ok(jobs)
+    }
+
+    async fn renew_lease(
+        &self,
+        job_id: &str,
+        worker_id: &str,
+        claim_token: &str,
+        now_ms: u64,
+        lease_ttl_ms: u64,
+    ) -> Result<()> {
+        let claimed_until_ms = (now_ms + lease_ttl_ms) as i64;
+        let result = sqlx::query(
+            "UPDATE cron_jobs SET claimed_until_ms = $1
+             WHERE id = $2 AND claimed_by = $3 AND claim_token = $4",
+        )
+        .bind(claimed_until_ms)
+        .bind(job_id)
+        .bind(worker_id)
+        .bind(claim_token)
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            anyhow::bail!("lease not held by {worker_id} (claim {claim_token}) for job {job_id}");
+        }
+        Ok(())
+    }
+
+    async fn release_claim(&self, job_id: &str, worker_id: &str, claim_token: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE cron_jobs SET claimed_by = NULL, claimed_until_ms = NULL, claim_token = NULL
+             WHERE id = $1 AND claimed_by = $2 AND claim_token = $3",
+        )
+        .bind(job_id)
+        .bind(worker_id)
+        .bind(claim_token)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::types::*};
+
+    fn make_job(id: &str) -> CronJob {
+        CronJob {
+            id: id.into(),
+            name: format!("job-{id}"),
+            enabled: true,
+            delete_after_run: false,
+            schedule: CronSchedule::At { at_ms: 1000 },
+            payload: CronPayload::SystemEvent { text: "hi".into() },
+            session_target: SessionTarget::Main,
+            state: CronJobState::default(),
+            retention: RunRetention::default(),
+            retry: RetryPolicy::default(),
+            created_at_ms: 1000,
+            updated_at_ms: 1000,
+        }
+    }
+
+    // Requires a reachable Postgres instance; run with `TEST_DATABASE_URL` set.
+    async fn make_store() -> Option<PostgresStore> {
+        let url = std::env::var("TEST_DATABASE_URL").ok()?;
+        Some(PostgresStore::new(&url).await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_postgres_roundtrip() {
+        let Some(store) = make_store().await else {
+            return;
+        };
+        store.save_job(&make_job("pg-1")).await.unwrap();
+        let jobs = store.load_jobs().await.unwrap();
+        assert!(jobs.iter().any(|j| j.id == "pg-1"));
+    }
+
+    #[tokio::test]
+    async fn test_postgres_claim_due_jobs_skip_locked() {
+        let Some(store) = make_store().await else {
+            return;
+        };
+        let mut job = make_job("pg-due-1");
+        job.state.next_run_ms = Some(1000);
+        store.save_job(&job).await.unwrap();
+
+        let claimed = store
+            .claim_due_jobs("worker-a", 5000, 60_000, 10)
+            .await
+            .unwrap();
+        assert!(claimed.iter().any(|(j, _)| j.id == "pg-due-1"));
+
+        let second = store
+            .claim_due_jobs("worker-b", 5000, 60_000, 10)
+            .await
+            .unwrap();
+        assert!(!second.iter().any(|(j, _)| j.id == "pg-due-1"));
+    }
+
+    #[tokio::test]
+    async fn test_postgres_renew_lease_stale_token_fails() {
+        let Some(store) = make_store().await else {
+            return;
+        };
+        let mut job = make_job("pg-due-2");
+        job.state.next_run_ms = Some(1000);
+        store.save_job(&job).await.unwrap();
+
+        let first_claim = store.claim_due_jobs("worker-a", 5000, 1000, 10).await.unwrap();
+        let (_, stale_token) = first_claim
+            .into_iter()
+            .find(|(j, _)| j.id == "pg-due-2")
+            .unwrap();
+
+        store
+            .release_claim("pg-due-2", "worker-a", &stale_token)
+            .await
+            .unwrap();
+        let second_claim = store.claim_due_jobs("worker-a", 5500, 1000, 10).await.unwrap();
+        let (_, current_token) = second_claim
+            .into_iter()
+            .find(|(j, _)| j.id == "pg-due-2")
+            .unwrap();
+        assert_ne!(stale_token, current_token);
+
+        assert!(
+            store
+                .renew_lease("pg-due-2", "worker-a", &stale_token, 6000, 1000)
+                .await
+                .is_err(),
+            "a stale claim token must not renew the current claim"
+        );
+    }
+}
@@ -0,0 +1,447 @@
+//! Embedded, pure-Rust cron store backed by [`sled`], for deployments that
+//! can't link the SQLite C library (or want a single-file artifact without a
+//! database server).
+//!
+//! One tree holds jobs keyed by `job.id`; a second holds runs keyed by
+//! `(job_id, started_at_ms)` as a big-endian byte tuple so a reverse range
+//! scan yields the most recent runs first without a secondary index.
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    store::CronStore,
+    types::{CronJob, CronRunRecord, RunStatus},
+};
+
+/// sled-backed persistence for cron jobs and run history.
+pub struct SledStore {
+    jobs: sled::Tree,
+    runs: sled::Tree,
+}
+
+impl SledStore {
+    /// Open (or create) a sled database at `path`.
+    pub fn open(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let db = sled::open(path).context("failed to open sled database")?;
+        let jobs = db.open_tree("cron_jobs").context("failed to open cron_jobs tree")?;
+        let runs = db.open_tree("cron_runs").context("failed to open cron_runs tree")?;
+        Ok(Self { jobs, runs })
+    }
+
+    fn run_key(job_id: &str, started_at_ms: u64) -> Vec<u8> {
+        let mut key = Vec::with_capacity(job_id.len() + 1 + 8);
+        key.extend_from_slice(job_id.as_bytes());
+        key.push(0); // separator; job ids can't contain NUL
+        key.extend_from_slice(&started_at_ms.to_be_bytes());
+        key
+    }
+
+    fn run_key_prefix(job_id: &str) -> Vec<u8> {
+        let mut key = Vec::with_capacity(job_id.len() + 1);
+        key.extend_from_slice(job_id.as_bytes());
+        key.push(0);
+        key
+    }
+}
+
+#[async_trait]
+impl CronStore for SledStore {
+    async fn load_jobs(&self) -> Result<Vec<CronJob>> {
+        let mut jobs = Vec::new();
+        for entry in self.jobs.iter() {
+            let (_, value) = entry?;
+            jobs.push(serde_json::from_slice(&value)?);
+        }
+        Ok(jobs)
+    }
+
+    async fn save_job(&self, job: &CronJob) -> Result<()> {
+        let data = serde_json::to_vec(job)?;
+        self.jobs.insert(job.id.as_bytes(), data)?;
+        self.jobs.flush_async().await?;
+        Ok(())
+    }
+
+    async fn delete_job(&self, id: &str) -> Result<()> {
+        let removed = self.jobs.remove(id.as_bytes())?;
+        if removed.is_none() {
+            anyhow::bail!("job not found: {id}");
+        }
+        // Drop the job's run history along with it.
+        let prefix = Self::run_key_prefix(id);
+        for entry in self.runs.scan_prefix(&prefix) {
+            let (key, _) = entry?;
+            self.runs.remove(key)?;
+        }
+        self.jobs.flush_async().await?;
+        self.runs.flush_async().await?;
+        Ok(())
+    }
+
+    async fn update_job(&self, job: &CronJob) -> Result<()> {
+        if !self.jobs.contains_key(job.id.as_bytes())? {
+            anyhow::bail!("job not found: {}", job.id);
+        }
+        let data = serde_json::to_vec(job)?;
+        self.jobs.insert(job.id.as_bytes(), data)?;
+        self.jobs.flush_async().await?;
+        Ok(())
+    }
+
+    async fn append_run(&self, job_id: &str, run: &CronRunRecord) -> Result<()> {
+        let key = Self::run_key(job_id, run.started_at_ms);
+        let data = serde_json::to_vec(run)?;
+        self.runs.insert(key, data)?;
+        self.runs.flush_async().await?;
+        Ok(())
+    }
+
+    async fn get_runs(
+        &self,
+        job_id: &str,
+        limit: usize,
+        status_filter: Option<RunStatus>,
+    ) -> Result<Vec<CronRunRecord>> {
+        let prefix = Self::run_key_prefix(job_id);
+        let mut runs = Vec::new();
+        // Reverse scan within the prefix yields newest-first.
+        for entry in self.runs.scan_prefix(&prefix).rev() {
+            let (_, value) = entry?;
+            let run: CronRunRecord = serde_json::from_slice(&value)?;
+            if status_filter.is_some_and(|s| s != run.status) {
+                continue;
+            }
+            runs.push(run);
+            if runs.len() == limit {
+                break;
+            }
+        }
+        // Reverse so oldest first, matching the other stores.
+        runs.reverse();
+        Ok(runs)
+    }
+
+    async fn count_runs_by_status(&self, job_id: &str) -> Result<Vec<(RunStatus, u64)>> {
+        let prefix = Self::run_key_prefix(job_id);
+        let mut ok = 0u64;
+        let mut error = 0u64;
+        let mut skipped = 0u64;
+        for entry in self.runs.scan_prefix(&prefix) {
+            let (_, value) = entry?;
+            let run: CronRunRecord = serde_json::from_slice(&value)?;
+            match run.status {
+                RunStatus::Ok => ok += 1,
+                RunStatus::Error => error += 1,
+                RunStatus::Skipped => skipped += 1,
+            }
+        }
+        Ok(vec![
+            (RunStatus::Ok, ok),
+            (RunStatus::Error, error),
+            (RunStatus::Skipped, skipped),
+        ])
+    }
+
+    async fn prune_runs(&self, job_id: &str, keep: usize, max_age_ms: Option<u64>, now_ms: u64) -> Result<()> {
+        let prefix = Self::run_key_prefix(job_id);
+        let mut keys: Vec<Arc<[u8]>> = self
+            .runs
+            .scan_prefix(&prefix)
+            .map(|entry| entry.map(|(k, _)| k))
+            .collect::<Result<_, _>>()?;
+        // Keys sort ascending by started_at_ms; drop everything but the tail.
+        if keys.len() > keep {
+            for key in keys.drain(..keys.len() - keep) {
+                self.runs.remove(key)?;
+            }
+        }
+
+        if let Some(max_age_ms) = max_age_ms {
+            let cutoff = now_ms.saturating_sub(max_age_ms);
+            for key in &keys {
+                let started_at_ms = u64::from_be_bytes(key[key.len() - 8..].try_into()?);
+                if started_at_ms < cutoff {
+                    self.runs.remove(key.as_ref())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn claim_due_jobs(
+        &self,
+        worker_id: &str,
+        now_ms: u64,
+        lease_ttl_ms: u64,
+        limit: usize,
+    ) -> Result<Vec<(CronJob, String)>> {
+        let mut claimed = Vec::new();
+        for entry in self.jobs.iter() {
+            if claimed.len() == limit {
+                break;
+            }
+            let (key, value) = entry?;
+            let job: CronJob = serde_json::from_slice(&value)?;
+
+            let due = job.state.next_run_ms.is_some_and(|t| t <= now_ms);
+            let available = job
+                .state
+                .claimed_until_ms
+                .map_or(true, |until| until < now_ms);
+            if !due || !available {
+                continue;
+            }
+
+            let mut updated = job.clone();
+            let claim_token = Uuid::new_v4().to_string();
+            updated.state.claimed_by = Some(worker_id.to_string());
+            updated.state.claimed_until_ms = Some(now_ms + lease_ttl_ms);
+            updated.state.claim_token = Some(claim_token.clone());
+            let new_value = serde_json::to_vec(&updated)?;
+
+            // Atomic claim: only succeeds if nobody else modified the row first.
+            if self
+                .jobs
+                .compare_and_swap(&key, Some(value), Some(new_value))?
+                .is_ok()
+            {
+                claimed.push((updated, claim_token));
+            }
+        }
+        self.jobs.flush_async().await?;
+        Ok(claimed)
+    }
+
+    async fn renew_lease(
+        &self,
+        job_id: &str,
+        worker_id: &str,
+        claim_token: &str,
+        now_ms: u64,
+        lease_ttl_ms: u64,
+    ) -> Result<()> {
+        let Some(value) = self.jobs.get(job_id.as_bytes())? else {
+            anyhow::bail!("job not found: {job_id}");
+        };
+        let mut job: CronJob = serde_json::from_slice(&value)?;
+        if job.state.claimed_by.as_deref() != Some(worker_id)
+            || job.state.claim_token.as_deref() != Some(claim_token)
+        {
+            anyhow::bail!("lease not held by {worker_id} (claim {claim_token}) for job {job_id}");
+        }
+        job.state.claimed_until_ms = Some(now_ms + lease_ttl_ms);
+        self.jobs
+            .insert(job_id.as_bytes(), serde_json::to_vec(&job)?)?;
+        self.jobs.flush_async().await?;
+        Ok(())
+    }
+
+    async fn release_claim(&self, job_id: &str, worker_id: &str, claim_token: &str) -> Result<()> {
+        let Some(value) = self.jobs.get(job_id.as_bytes())? else {
+            return Ok(());
+        };
+        let mut job: CronJob = serde_json::from_slice(&value)?;
+        if job.state.claimed_by.as_deref() != Some(worker_id)
+            || job.state.claim_token.as_deref() != Some(claim_token)
+        {
+            return Ok(());
+        }
+        job.state.claimed_by = None;
+        job.state.claimed_until_ms = None;
+        job.state.claim_token = None;
+        self.jobs
+            .insert(job_id.as_bytes(), serde_json::to_vec(&job)?)?;
+        self.jobs.flush_async().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::types::*, tempfile::TempDir};
+
+    fn make_job(id: &str) -> CronJob {
+        CronJob {
+            id: id.into(),
+            name: format!("job-{id}"),
+            enabled: true,
+            delete_after_run: false,
+            schedule: CronSchedule::At { at_ms: 1000 },
+            payload: CronPayload::SystemEvent { text: "hi".into() },
+            session_target: SessionTarget::Main,
+            state: CronJobState::default(),
+            retention: RunRetention::default(),
+            retry: RetryPolicy::default(),
+            created_at_ms: 1000,
+            updated_at_ms: 1000,
+        }
+    }
+
+    fn make_store() -> (SledStore, TempDir) {
+        let dir = TempDir::new().unwrap();
+        let store = SledStore::open(dir.path().join("cron.sled")).unwrap();
+        (store, dir)
+    }
+
+    #[tokio::test]
+    async fn test_sled_roundtrip() {
+        let (store, _dir) = make_store();
+        store.save_job(&make_job("1")).await.unwrap();
+        store.save_job(&make_job("2")).await.unwrap();
+        let jobs = store.load_jobs().await.unwrap();
+        assert_eq!(jobs.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sled_update_not_found() {
+        let (store, _dir) = make_store();
+        assert!(store.update_job(&make_job("nope")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sled_delete_not_found() {
+        let (store, _dir) = make_store();
+        assert!(store.delete_job("nope").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sled_delete_removes_runs() {
+        let (store, _dir) = make_store();
+        store.save_job(&make_job("1")).await.unwrap();
+        let run = CronRunRecord {
+            job_id: "1".into(),
+            started_at_ms: 1000,
+            finished_at_ms: 1500,
+            status: RunStatus::Ok,
+            error: None,
+            duration_ms: 500,
+            output: None,
+            attempt: 1,
+        };
+        store.append_run("1", &run).await.unwrap();
+        store.delete_job("1").await.unwrap();
+        assert!(store.get_runs("1", 10, None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sled_get_runs_oldest_first() {
+        let (store, _dir) = make_store();
+        store.save_job(&make_job("1")).await.unwrap();
+        for i in 0..5 {
+            let run = CronRunRecord {
+                job_id: "1".into(),
+                started_at_ms: i * 1000,
+                finished_at_ms: i * 1000 + 500,
+                status: RunStatus::Ok,
+                error: None,
+                duration_ms: 500,
+                output: None,
+                attempt: 1,
+            };
+            store.append_run("1", &run).await.unwrap();
+        }
+        let runs = store.get_runs("1", 3, None).await.unwrap();
+        assert_eq!(runs.len(), 3);
+        assert_eq!(runs[0].started_at_ms, 2000);
+        assert_eq!(runs[2].started_at_ms, 4000);
+    }
+
+    #[tokio::test]
+    async fn test_sled_prune_runs() {
+        let (store, _dir) = make_store();
+        store.save_job(&make_job("1")).await.unwrap();
+        for i in 0..10 {
+            let run = CronRunRecord {
+                job_id: "1".into(),
+                started_at_ms: i * 1000,
+                finished_at_ms: i * 1000 + 500,
+                status: RunStatus::Ok,
+                error: None,
+                duration_ms: 500,
+                output: None,
+                attempt: 1,
+            };
+            store.append_run("1", &run).await.unwrap();
+        }
+        store.prune_runs("1", 3, None, 9000).await.unwrap();
+        let runs = store.get_runs("1", 100, None).await.unwrap();
+        assert_eq!(runs.len(), 3);
+        assert_eq!(runs[2].started_at_ms, 9000);
+    }
+
+    #[tokio::test]
+    async fn test_sled_prune_runs_by_age() {
+        let (store, _dir) = make_store();
+        store.save_job(&make_job("1")).await.unwrap();
+        for i in 0..10 {
+            let run = CronRunRecord {
+                job_id: "1".into(),
+                started_at_ms: i * 1000,
+                finished_at_ms: i * 1000 + 500,
+                status: RunStatus::Ok,
+                error: None,
+                duration_ms: 500,
+                output: None,
+                attempt: 1,
+            };
+            store.append_run("1", &run).await.unwrap();
+        }
+
+        // keep=100 (no-op), but drop anything older than 3500ms before now=9000,
+        // i.e. started_at_ms < 5500.
+        store.prune_runs("1", 100, Some(3500), 9000).await.unwrap();
+
+        let runs = store.get_runs("1", 100, None).await.unwrap();
+        assert_eq!(runs.len(), 4);
+        assert_eq!(runs[0].started_at_ms, 6000);
+        assert_eq!(runs[3].started_at_ms, 9000);
+    }
+
+    #[tokio::test]
+    async fn test_sled_claim_due_jobs() {
+        let (store, _dir) = make_store();
+        let mut job = make_job("1");
+        job.state.next_run_ms = Some(1000);
+        store.save_job(&job).await.unwrap();
+
+        let claimed = store.claim_due_jobs("worker-a", 5000, 60_000, 10).await.unwrap();
+        assert_eq!(claimed.len(), 1);
+
+        let second = store.claim_due_jobs("worker-b", 5000, 60_000, 10).await.unwrap();
+        assert!(second.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sled_renew_lease_stale_token_fails() {
+        let (store, _dir) = make_store();
+        let mut job = make_job("1");
+        job.state.next_run_ms = Some(1000);
+        store.save_job(&job).await.unwrap();
+
+        let first_claim = store.claim_due_jobs("worker-a", 5000, 1000, 10).await.unwrap();
+        let stale_token = first_claim[0].1.clone();
+
+        store.release_claim("1", "worker-a", &stale_token).await.unwrap();
+        let second_claim = store.claim_due_jobs("worker-a", 5500, 1000, 10).await.unwrap();
+        assert_ne!(stale_token, second_claim[0].1);
+
+        assert!(
+            store
+                .renew_lease("1", "worker-a", &stale_token, 6000, 1000)
+                .await
+                .is_err(),
+            "a stale claim token must not renew the current claim"
+        );
+
+        store.release_claim("1", "worker-a", &stale_token).await.unwrap();
+        assert!(
+            store.claim_due_jobs("worker-b", 6001, 1000, 10).await.unwrap().is_empty(),
+            "a stale-token release must not clear the current claim either"
+        );
+    }
+}
@@ -4,11 +4,12 @@ use {
     anyhow::{Context, Result},
     async_trait::async_trait,
     sqlx::{Row, SqlitePool, sqlite::SqlitePoolOptions},
+    uuid::Uuid,
 };
 
 use crate::{
     store::CronStore,
-    types::{CronJob, CronRunRecord},
+    types::{CronJob, CronRunRecord, RunStatus},
 };
 
 /// SQLite-backed persistence for cron jobs and run history.
@@ -28,34 +29,84 @@ impl SqliteStore {
         sqlx::query(
             "CREATE TABLE IF NOT EXISTS cron_jobs (
                 id TEXT PRIMARY KEY,
-                data TEXT NOT NULL
+                data TEXT NOT NULL,
+                next_run_ms INTEGER,
+                claimed_by TEXT,
+                claimed_until_ms INTEGER,
+                claim_token TEXT
             )",
         )
         .execute(&pool)
         .await?;
 
+        // Upgrade path for databases created before claiming support was added.
+        // SQLite has no `ADD COLUMN IF NOT EXISTS`, so ignore "duplicate column".
+        for stmt in [
+            "ALTER TABLE cron_jobs ADD COLUMN next_run_ms INTEGER",
+            "ALTER TABLE cron_jobs ADD COLUMN claimed_by TEXT",
+            "ALTER TABLE cron_jobs ADD COLUMN claimed_until_ms INTEGER",
+            "ALTER TABLE cron_jobs ADD COLUMN claim_token TEXT",
+        ] {
+            if let Err(e) = sqlx::query(stmt).execute(&pool).await {
+                if !e.to_string().contains("duplicate column name") {
+                    return Err(e).context("failed to upgrade cron_jobs schema");
+                }
+            }
+        }
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_cron_jobs_due ON cron_jobs(next_run_ms, claimed_until_ms)",
+        )
+        .execute(&pool)
+        .await?;
+
         sqlx::query(
             "CREATE TABLE IF NOT EXISTS cron_runs (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 job_id TEXT NOT NULL,
                 started_at_ms INTEGER NOT NULL,
                 finished_at_ms INTEGER NOT NULL,
-                status TEXT NOT NULL,
+                status TEXT NOT NULL CHECK (status IN ('ok', 'error', 'skipped')),
                 error TEXT,
                 duration_ms INTEGER NOT NULL,
                 output TEXT,
+                attempt INTEGER,
                 FOREIGN KEY (job_id) REFERENCES cron_jobs(id)
             )",
         )
         .execute(&pool)
         .await?;
 
+        // Upgrade path for databases created before attempt tracking was added.
+        if let Err(e) = sqlx::query("ALTER TABLE cron_runs ADD COLUMN attempt INTEGER")
+            .execute(&pool)
+            .await
+        {
+            if !e.to_string().contains("duplicate column name") {
+                return Err(e).context("failed to upgrade cron_runs schema");
+            }
+        }
+
         sqlx::query(
             "CREATE INDEX IF NOT EXISTS idx_cron_runs_job_id ON cron_runs(job_id, started_at_ms DESC)",
         )
         .execute(&pool)
         .await?;
 
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_cron_runs_job_status
+             ON cron_runs(job_id, status, started_at_ms DESC)",
+        )
+        .execute(&pool)
+        .await?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_cron_runs_job_attempt
+             ON cron_runs(job_id, started_at_ms, attempt)",
+        )
+        .execute(&pool)
+        .await?;
+
         Ok(Self { pool })
     }
 }
@@ -78,12 +129,14 @@ impl CronStore for SqliteStore {
 
     async fn save_job(&self, job: &CronJob) -> Result<()> {
         let data = serde_json::to_string(job)?;
+        let next_run_ms = job.state.next_run_ms.map(|v| v as i64);
         sqlx::query(
-            "INSERT INTO cron_jobs (id, data) VALUES (?, ?)
-             ON CONFLICT(id) DO UPDATE SET data = excluded.data",
+            "INSERT INTO cron_jobs (id, data, next_run_ms) VALUES (?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET data = excluded.data, next_run_ms = excluded.next_run_ms",
         )
         .bind(&job.id)
         .bind(&data)
+        .bind(next_run_ms)
         .execute(&self.pool)
         .await?;
         Ok(())
@@ -102,8 +155,10 @@ impl CronStore for SqliteStore {
 
     async fn update_job(&self, job: &CronJob) -> Result<()> {
         let data = serde_json::to_string(job)?;
-        let result = sqlx::query("UPDATE cron_jobs SET data = ? WHERE id = ?")
+        let next_run_ms = job.state.next_run_ms.map(|v| v as i64);
+        let result = sqlx::query("UPDATE cron_jobs SET data = ?, next_run_ms = ? WHERE id = ?")
             .bind(&data)
+            .bind(next_run_ms)
             .bind(&job.id)
             .execute(&self.pool)
             .await?;
@@ -114,40 +169,64 @@ impl CronStore for SqliteStore {
     }
 
     async fn append_run(&self, job_id: &str, run: &CronRunRecord) -> Result<()> {
-        let status = serde_json::to_string(&run.status)?;
         sqlx::query(
-            "INSERT INTO cron_runs (job_id, started_at_ms, finished_at_ms, status, error, duration_ms, output)
-             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO cron_runs (job_id, started_at_ms, finished_at_ms, status, error, duration_ms, output, attempt)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(job_id)
         .bind(run.started_at_ms as i64)
         .bind(run.finished_at_ms as i64)
-        .bind(&status)
+        .bind(run.status.as_str())
         .bind(&run.error)
         .bind(run.duration_ms as i64)
         .bind(&run.output)
+        .bind(run.attempt as i64)
         .execute(&self.pool)
         .await?;
         Ok(())
     }
 
-    async fn get_runs(&self, job_id: &str, limit: usize) -> Result<Vec<CronRunRecord>> {
-        let rows = sqlx::query(
-            "SELECT job_id, started_at_ms, finished_at_ms, status, error, duration_ms, output
-             FROM cron_runs
-             WHERE job_id = ?
-             ORDER BY started_at_ms DESC
-             LIMIT ?",
-        )
-        .bind(job_id)
-        .bind(limit as i64)
-        .fetch_all(&self.pool)
-        .await?;
+    async fn get_runs(
+        &self,
+        job_id: &str,
+        limit: usize,
+        status_filter: Option<RunStatus>,
+    ) -> Result<Vec<CronRunRecord>> {
+        let rows = match status_filter {
+            Some(status) => {
+                sqlx::query(
+                    "SELECT job_id, started_at_ms, finished_at_ms, status, error, duration_ms, output, attempt
+                     FROM cron_runs
+                     WHERE job_id = ? AND status = ?
+                     ORDER BY started_at_ms DESC
+                     LIMIT ?",
+                )
+                .bind(job_id)
+                .bind(status.as_str())
+                .bind(limit as i64)
+                .fetch_all(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query(
+                    "SELECT job_id, started_at_ms, finished_at_ms, status, error, duration_ms, output, attempt
+                     FROM cron_runs
+                     WHERE job_id = ?
+                     ORDER BY started_at_ms DESC
+                     LIMIT ?",
+                )
+                .bind(job_id)
+                .bind(limit as i64)
+                .fetch_all(&self.pool)
+                .await?
+            }
+        };
 
         let mut runs = Vec::with_capacity(rows.len());
         for row in rows {
             let status_str: String = row.get("status");
-            let status = serde_json::from_str(&status_str)?;
+            let status: RunStatus = status_str.parse()?;
+            let attempt = row.get::<Option<i64>, _>("attempt").unwrap_or(1) as u32;
             runs.push(CronRunRecord {
                 job_id: row.get("job_id"),
                 started_at_ms: row.get::<i64, _>("started_at_ms") as u64,
@@ -156,12 +235,146 @@ impl CronStore for SqliteStore {
                 error: row.get("error"),
                 duration_ms: row.get::<i64, _>("duration_ms") as u64,
                 output: row.get("output"),
+                attempt,
             });
         }
         // Reverse so oldest first (consistent with other stores).
         runs.reverse();
         Ok(runs)
     }
+
+    async fn count_runs_by_status(&self, job_id: &str) -> Result<Vec<(RunStatus, u64)>> {
+        let rows = sqlx::query(
+            "SELECT status, COUNT(*) as n FROM cron_runs WHERE job_id = ? GROUP BY status",
+        )
+        .bind(job_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut counts = Vec::with_capacity(rows.len());
+        for row in rows {
+            let status_str: String = row.get("status");
+            let status: RunStatus = status_str.parse()?;
+            let n: i64 = row.get("n");
+            counts.push((status, n as u64));
+        }
+        Ok(counts)
+    }
+
+    async fn prune_runs(&self, job_id: &str, keep: usize, max_age_ms: Option<u64>, now_ms: u64) -> Result<()> {
+        sqlx::query(
+            "DELETE FROM cron_runs WHERE job_id = ? AND id NOT IN (
+                 SELECT id FROM cron_runs WHERE job_id = ? ORDER BY started_at_ms DESC LIMIT ?
+             )",
+        )
+        .bind(job_id)
+        .bind(job_id)
+        .bind(keep as i64)
+        .execute(&self.pool)
+        .await?;
+
+        if let Some(max_age_ms) = max_age_ms {
+            let cutoff = now_ms.saturating_sub(max_age_ms) as i64;
+            sqlx::query("DELETE FROM cron_runs WHERE job_id = ? AND started_at_ms < ?")
+                .bind(job_id)
+                .bind(cutoff)
+                .execute(&self.pool)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn claim_due_jobs(
+        &self,
+        worker_id: &str,
+        now_ms: u64,
+        lease_ttl_ms: u64,
+        limit: usize,
+    ) -> Result<Vec<(CronJob, String)>> {
+        // SQLite has no `SELECT ... FOR UPDATE SKIP LOCKED`; rely on the
+        // connection's busy-timeout plus an UPDATE...RETURNING guarded by the
+        // same claimed_until_ms predicate to make claiming effectively atomic
+        // under SQLite's single-writer model.
+        let claimed_until_ms = (now_ms + lease_ttl_ms) as i64;
+        let rows = sqlx::query(
+            "UPDATE cron_jobs SET claimed_by = ?, claimed_until_ms = ?
+             WHERE id IN (
+                 SELECT id FROM cron_jobs
+                 WHERE next_run_ms IS NOT NULL AND next_run_ms <= ?
+                   AND (claimed_until_ms IS NULL OR claimed_until_ms < ?)
+                 ORDER BY next_run_ms
+                 LIMIT ?
+             )
+             RETURNING id, data",
+        )
+        .bind(worker_id)
+        .bind(claimed_until_ms)
+        .bind(now_ms as i64)
+        .bind(now_ms as i64)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        // Stamp each newly claimed row with its own claim token in a second
+        // pass, rather than binding one token for the whole batch, so two
+        // jobs claimed in the same call don't share a fencing token.
+        let mut jobs = Vec::with_capacity(rows.len());
+        for row in rows {
+            let id: String = row.get("id");
+            let data: String = row.get("data");
+            let job: CronJob = serde_json::from_str(&data)?;
+
+            let claim_token = Uuid::new_v4().to_string();
+            sqlx::query("UPDATE cron_jobs SET claim_token = ? WHERE id = ? AND claimed_by = ?")
+                .bind(&claim_token)
+                .bind(&id)
+                .bind(worker_id)
+                .execute(&self.pool)
+                .await?;
+
+            jobs.push((job, claim_token));
+        }
+        Ok(jobs)
+    }
+
+    async fn renew_lease(
+        &self,
+        job_id: &str,
+        worker_id: &str,
+        claim_token: &str,
+        now_ms: u64,
+        lease_ttl_ms: u64,
+    ) -> Result<()> {
+        let claimed_until_ms = (now_ms + lease_ttl_ms) as i64;
+        let result = sqlx::query(
+            "UPDATE cron_jobs SET claimed_until_ms = ?
+             WHERE id = ? AND claimed_by = ? AND claim_token = ?",
+        )
+        .bind(claimed_until_ms)
+        .bind(job_id)
+        .bind(worker_id)
+        .bind(claim_token)
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            anyhow::bail!("lease not held by {worker_id} (claim {claim_token}) for job {job_id}");
+        }
+        Ok(())
+    }
+
+    async fn release_claim(&self, job_id: &str, worker_id: &str, claim_token: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE cron_jobs SET claimed_by = NULL, claimed_until_ms = NULL, claim_token = NULL
+             WHERE id = ? AND claimed_by = ? AND claim_token = ?",
+        )
+        .bind(job_id)
+        .bind(worker_id)
+        .bind(claim_token)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -182,6 +395,8 @@ mod tests {
             payload: CronPayload::SystemEvent { text: "hi".into() },
             session_target: SessionTarget::Main,
             state: CronJobState::default(),
+            retention: RunRetention::default(),
+            retry: RetryPolicy::default(),
             created_at_ms: 1000,
             updated_at_ms: 1000,
         }
@@ -252,11 +467,12 @@ mod tests {
                 error: None,
                 duration_ms: 500,
                 output: None,
+                attempt: 1,
             };
             store.append_run("j1", &run).await.unwrap();
         }
 
-        let runs = store.get_runs("j1", 3).await.unwrap();
+        let runs = store.get_runs("j1", 3, None).await.unwrap();
         assert_eq!(runs.len(), 3);
         // Should be the last 3, in chronological order
         assert_eq!(runs[0].started_at_ms, 2000);
@@ -266,7 +482,247 @@ mod tests {
     #[tokio::test]
     async fn test_sqlite_runs_empty() {
         let store = make_store().await;
-        let runs = store.get_runs("none", 10).await.unwrap();
+        let runs = store.get_runs("none", 10, None).await.unwrap();
         assert!(runs.is_empty());
     }
+
+    fn make_due_job(id: &str, next_run_ms: u64) -> CronJob {
+        let mut job = make_job(id);
+        job.state.next_run_ms = Some(next_run_ms);
+        job
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_claim_due_jobs() {
+        let store = make_store().await;
+        store.save_job(&make_due_job("due-1", 1000)).await.unwrap();
+        store.save_job(&make_due_job("due-2", 2000)).await.unwrap();
+        store
+            .save_job(&make_due_job("not-due", 9_999_999))
+            .await
+            .unwrap();
+
+        let claimed = store.claim_due_jobs("worker-a", 5000, 60_000, 10).await.unwrap();
+        assert_eq!(claimed.len(), 2);
+        assert_eq!(claimed[0].0.id, "due-1");
+        assert_eq!(claimed[1].0.id, "due-2");
+        assert_ne!(claimed[0].1, claimed[1].1, "each claimed job gets its own claim token");
+
+        // A second worker polling immediately sees nothing left to claim.
+        let second = store.claim_due_jobs("worker-b", 5000, 60_000, 10).await.unwrap();
+        assert!(second.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_claim_reclaims_after_lease_expiry() {
+        let store = make_store().await;
+        store.save_job(&make_due_job("due-1", 1000)).await.unwrap();
+
+        store.claim_due_jobs("worker-a", 5000, 1000, 10).await.unwrap();
+        // Before the lease expires, another worker gets nothing.
+        assert!(
+            store
+                .claim_due_jobs("worker-b", 5500, 1000, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        // After expiry, it becomes claimable again.
+        let reclaimed = store.claim_due_jobs("worker-b", 6001, 1000, 10).await.unwrap();
+        assert_eq!(reclaimed.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_renew_lease_and_release() {
+        let store = make_store().await;
+        store.save_job(&make_due_job("due-1", 1000)).await.unwrap();
+        let claimed = store.claim_due_jobs("worker-a", 5000, 1000, 10).await.unwrap();
+        let claim_token = &claimed[0].1;
+
+        store.renew_lease("due-1", "worker-a", claim_token, 5500, 1000).await.unwrap();
+        assert!(
+            store
+                .claim_due_jobs("worker-b", 6001, 1000, 10)
+                .await
+                .unwrap()
+                .is_empty(),
+            "renewed lease should still be held"
+        );
+
+        store.release_claim("due-1", "worker-a", claim_token).await.unwrap();
+        let claimed = store.claim_due_jobs("worker-b", 6001, 1000, 10).await.unwrap();
+        assert_eq!(claimed.len(), 1, "released job should be claimable again");
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_renew_lease_wrong_worker_fails() {
+        let store = make_store().await;
+        store.save_job(&make_due_job("due-1", 1000)).await.unwrap();
+        let claimed = store.claim_due_jobs("worker-a", 5000, 1000, 10).await.unwrap();
+        let claim_token = &claimed[0].1;
+
+        assert!(
+            store
+                .renew_lease("due-1", "worker-b", claim_token, 5500, 1000)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_renew_lease_stale_token_fails() {
+        let store = make_store().await;
+        store.save_job(&make_due_job("due-1", 1000)).await.unwrap();
+        let first_claim = store.claim_due_jobs("worker-a", 5000, 1000, 10).await.unwrap();
+        let stale_token = first_claim[0].1.clone();
+
+        // The claim is released and the same worker re-claims the job, minting a
+        // new claim token. A heartbeat still holding the old token must not
+        // be able to renew or release the new claim.
+        store.release_claim("due-1", "worker-a", &stale_token).await.unwrap();
+        let second_claim = store.claim_due_jobs("worker-a", 5500, 1000, 10).await.unwrap();
+        assert_ne!(stale_token, second_claim[0].1);
+
+        assert!(
+            store
+                .renew_lease("due-1", "worker-a", &stale_token, 6000, 1000)
+                .await
+                .is_err(),
+            "a stale claim token must not renew the current claim"
+        );
+
+        store.release_claim("due-1", "worker-a", &stale_token).await.unwrap();
+        assert!(
+            store
+                .claim_due_jobs("worker-b", 6001, 1000, 10)
+                .await
+                .unwrap()
+                .is_empty(),
+            "a stale-token release must not clear the current claim either"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_get_runs_status_filter() {
+        let store = make_store().await;
+        store.save_job(&make_job("j1")).await.unwrap();
+
+        for (i, status) in [RunStatus::Ok, RunStatus::Error, RunStatus::Ok].into_iter().enumerate() {
+            let run = CronRunRecord {
+                job_id: "j1".into(),
+                started_at_ms: i as u64 * 1000,
+                finished_at_ms: i as u64 * 1000 + 500,
+                status,
+                error: None,
+                duration_ms: 500,
+                output: None,
+                attempt: 1,
+            };
+            store.append_run("j1", &run).await.unwrap();
+        }
+
+        let ok_runs = store
+            .get_runs("j1", 10, Some(RunStatus::Ok))
+            .await
+            .unwrap();
+        assert_eq!(ok_runs.len(), 2);
+        assert!(ok_runs.iter().all(|r| r.status == RunStatus::Ok));
+
+        let error_runs = store
+            .get_runs("j1", 10, Some(RunStatus::Error))
+            .await
+            .unwrap();
+        assert_eq!(error_runs.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_count_runs_by_status() {
+        let store = make_store().await;
+        store.save_job(&make_job("j1")).await.unwrap();
+
+        for status in [RunStatus::Ok, RunStatus::Error, RunStatus::Ok] {
+            let run = CronRunRecord {
+                job_id: "j1".into(),
+                started_at_ms: 0,
+                finished_at_ms: 500,
+                status,
+                error: None,
+                duration_ms: 500,
+                output: None,
+                attempt: 1,
+            };
+            store.append_run("j1", &run).await.unwrap();
+        }
+
+        let counts = store.count_runs_by_status("j1").await.unwrap();
+        let ok_count = counts
+            .iter()
+            .find(|(s, _)| *s == RunStatus::Ok)
+            .map(|(_, n)| *n)
+            .unwrap();
+        let error_count = counts
+            .iter()
+            .find(|(s, _)| *s == RunStatus::Error)
+            .map(|(_, n)| *n)
+            .unwrap();
+        assert_eq!(ok_count, 2);
+        assert_eq!(error_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_prune_runs() {
+        let store = make_store().await;
+        store.save_job(&make_job("j1")).await.unwrap();
+
+        for i in 0..10 {
+            let run = CronRunRecord {
+                job_id: "j1".into(),
+                started_at_ms: i * 1000,
+                finished_at_ms: i * 1000 + 500,
+                status: RunStatus::Ok,
+                error: None,
+                duration_ms: 500,
+                output: None,
+                attempt: 1,
+            };
+            store.append_run("j1", &run).await.unwrap();
+        }
+
+        store.prune_runs("j1", 3, None, 9000).await.unwrap();
+
+        let runs = store.get_runs("j1", 100, None).await.unwrap();
+        assert_eq!(runs.len(), 3);
+        // Only the 3 most recent survive.
+        assert_eq!(runs[0].started_at_ms, 7000);
+        assert_eq!(runs[2].started_at_ms, 9000);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_prune_runs_by_age() {
+        let store = make_store().await;
+        store.save_job(&make_job("j1")).await.unwrap();
+
+        for i in 0..10 {
+            let run = CronRunRecord {
+                job_id: "j1".into(),
+                started_at_ms: i * 1000,
+                finished_at_ms: i * 1000 + 500,
+                status: RunStatus::Ok,
+                error: None,
+                duration_ms: 500,
+                output: None,
+                attempt: 1,
+            };
+            store.append_run("j1", &run).await.unwrap();
+        }
+
+        // keep=100 (no-op), but drop anything older than 3500ms before now=9000,
+        // i.e. started_at_ms < 5500.
+        store.prune_runs("j1", 100, Some(3500), 9000).await.unwrap();
+
+        let runs = store.get_runs("j1", 100, None).await.unwrap();
+        assert_eq!(runs.len(), 4);
+        assert_eq!(runs[0].started_at_ms, 6000);
+        assert_eq!(runs[3].started_at_ms, 9000);
+    }
 }
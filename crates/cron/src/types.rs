@@ -0,0 +1,195 @@
+//! Shared cron job/run data types.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// A scheduled job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CronJob {
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+    pub delete_after_run: bool,
+    pub schedule: CronSchedule,
+    pub payload: CronPayload,
+    pub session_target: SessionTarget,
+    pub state: CronJobState,
+    #[serde(default)]
+    pub retention: RunRetention,
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    pub created_at_ms: u64,
+    pub updated_at_ms: u64,
+}
+
+/// How much run history to keep for a job. `max_runs_retained: None` falls
+/// back to [`crate::service::DEFAULT_MAX_RUNS_RETAINED`]; `max_age_ms: None`
+/// applies no age limit.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct RunRetention {
+    /// Keep at most this many most-recent runs.
+    pub max_runs_retained: Option<u32>,
+    /// Additionally drop runs older than this, in milliseconds.
+    pub max_age_ms: Option<u64>,
+}
+
+/// When a job fires.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CronSchedule {
+    /// Fire once at an absolute timestamp.
+    At { at_ms: u64 },
+    /// Fire on a cron expression.
+    Cron { expr: String },
+}
+
+/// What a job does when it fires.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CronPayload {
+    /// Inject a synthetic system event into the target session.
+    SystemEvent { text: String },
+    /// Deliver an outbound HTTP request, with the response recorded as the run's output.
+    Webhook {
+        url: String,
+        #[serde(default = "default_webhook_method")]
+        method: String,
+        #[serde(default)]
+        headers: HashMap<String, String>,
+        #[serde(default)]
+        body: Option<String>,
+    },
+}
+
+fn default_webhook_method() -> String {
+    "POST".into()
+}
+
+/// Retry policy for payloads that can fail transiently (currently [`CronPayload::Webhook`]).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            backoff_ms: 1000,
+        }
+    }
+}
+
+/// Which session a fired job is delivered to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SessionTarget {
+    /// Run isolated, with no delivery.
+    Main,
+    /// Deliver to a named channel.
+    Channel { id: String },
+}
+
+/// Mutable runtime state tracked alongside a job definition.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CronJobState {
+    pub last_run_ms: Option<u64>,
+    pub next_run_ms: Option<u64>,
+    /// Worker id currently holding the claim lease, if any. SQL backends track
+    /// this in dedicated `claimed_by`/`claimed_until_ms` columns instead; it's
+    /// kept here too so backends without a separate column (e.g. sled) can
+    /// claim/lease directly on the job record.
+    #[serde(default)]
+    pub claimed_by: Option<String>,
+    #[serde(default)]
+    pub claimed_until_ms: Option<u64>,
+    /// Opaque token unique to the current claim, distinguishing it from a
+    /// later claim of the same job by the same `claimed_by` worker. Lets
+    /// `renew_lease`/`release_claim` reject a stale heartbeat from a claim
+    /// that's since been released and re-claimed.
+    #[serde(default)]
+    pub claim_token: Option<String>,
+}
+
+/// Outcome of a single run of a job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Ok,
+    Error,
+    Skipped,
+}
+
+impl RunStatus {
+    /// The discrete text value stored in the `cron_runs.status` column.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Error => "error",
+            Self::Skipped => "skipped",
+        }
+    }
+}
+
+impl std::str::FromStr for RunStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ok" => Ok(Self::Ok),
+            "error" => Ok(Self::Error),
+            "skipped" => Ok(Self::Skipped),
+            other => anyhow::bail!("unknown run status: {other}"),
+        }
+    }
+}
+
+/// A recorded run, persisted by [`crate::store::CronStore::append_run`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CronRunRecord {
+    pub job_id: String,
+    pub started_at_ms: u64,
+    pub finished_at_ms: u64,
+    pub status: RunStatus,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+    pub output: Option<String>,
+    /// 1-based attempt number within a single fire of the job. Payloads that
+    /// retry (e.g. [`CronPayload::Webhook`]) record one [`CronRunRecord`] per
+    /// attempt so `get_runs` shows the full delivery sequence.
+    #[serde(default = "default_attempt")]
+    pub attempt: u32,
+}
+
+fn default_attempt() -> u32 {
+    1
+}
+
+/// Fields accepted when creating a job through the API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CronJobCreate {
+    pub name: String,
+    pub schedule: CronSchedule,
+    pub payload: CronPayload,
+    #[serde(default)]
+    pub session_target: Option<SessionTarget>,
+    #[serde(default)]
+    pub delete_after_run: bool,
+    #[serde(default)]
+    pub retention: RunRetention,
+    #[serde(default)]
+    pub retry: RetryPolicy,
+}
+
+/// Partial update accepted through the API.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CronJobPatch {
+    pub name: Option<String>,
+    pub enabled: Option<bool>,
+    pub schedule: Option<CronSchedule>,
+    pub payload: Option<CronPayload>,
+    pub session_target: Option<SessionTarget>,
+    pub retention: Option<RunRetention>,
+}
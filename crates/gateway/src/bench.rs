@@ -0,0 +1,483 @@
+//! Load/latency self-benchmark harness.
+//!
+//! The boot-time integration test only measures a single `/health` round
+//! trip; this module is the reusable harness behind a `moltis bench`
+//! subcommand that drives configurable concurrent load against a chosen
+//! endpoint and reports latency percentiles, throughput, and a status-code
+//! histogram.
+//!
+//! [`Prober`] abstracts "make one request, time it" so the concurrency and
+//! stop-condition logic in [`run_bench`] can be unit tested against a fake
+//! prober instead of a real HTTP server; [`HttpProber`] is the implementation
+//! `moltis bench` would actually use. It speaks plain HTTP/1.1 over a raw
+//! `TcpStream` rather than going through `reqwest`, because `reqwest` (and
+//! most HTTP clients) don't expose per-request DNS/dial timestamps without a
+//! custom low-level connector — dialing the socket by hand is the smaller
+//! diff and gives [`ConnectionTime`] real numbers to report instead of an
+//! always-`None` placeholder.
+//!
+//! This checkout's gateway crate has no `main.rs` at all (only `cron.rs`,
+//! plus the other chunk3 modules), so there's no CLI to attach a `bench`
+//! subcommand and its `-z`/`-n`/`-c` flags to yet. [`BenchConfig`] carries
+//! exactly those three knobs so that wiring is a small diff once a CLI entry
+//! point exists; until then this module is blocked on that missing entry
+//! point, not on anything in here.
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::TcpStream,
+    sync::Mutex,
+};
+
+/// DNS-lookup and dial-up timestamps for a request that opened a fresh
+/// connection rather than reusing one from the pool.
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionTime {
+    pub dns_start: Instant,
+    pub dns_end: Instant,
+    pub dial_start: Instant,
+    pub dial_end: Instant,
+}
+
+impl ConnectionTime {
+    pub fn dns_lookup(&self) -> Duration {
+        self.dns_end.duration_since(self.dns_start)
+    }
+
+    pub fn dial(&self) -> Duration {
+        self.dial_end.duration_since(self.dial_start)
+    }
+
+    pub fn total(&self) -> Duration {
+        self.dial_end.duration_since(self.dns_start)
+    }
+}
+
+/// Outcome of a single probe request.
+#[derive(Debug, Clone)]
+pub struct RequestResult {
+    pub start: Instant,
+    pub end: Instant,
+    /// `None` when the request failed below the HTTP layer (connect error,
+    /// timeout, etc.) rather than completing with a status code.
+    pub status: Option<u16>,
+    pub body_len: usize,
+    pub connection_time: Option<ConnectionTime>,
+}
+
+impl RequestResult {
+    pub fn latency(&self) -> Duration {
+        self.end.duration_since(self.start)
+    }
+}
+
+/// Issues one timed request. Implemented by [`HttpProber`] for real runs and
+/// by fakes in tests.
+#[async_trait::async_trait]
+pub trait Prober: Send + Sync {
+    async fn probe(&self) -> RequestResult;
+}
+
+/// `host`, `port`, and `path` parsed out of a plain-HTTP bench target.
+struct ParsedUrl {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+fn parse_http_url(url: &str) -> anyhow::Result<ParsedUrl> {
+    let authority_and_path = url
+        .strip_prefix("http://")
+        .ok_or_else(|| anyhow::anyhow!("HttpProber only supports plain http:// URLs, got {url:?}"))?;
+    let (authority, path) = match authority_and_path.find('/') {
+        Some(idx) => (&authority_and_path[..idx], &authority_and_path[idx..]),
+        None => (authority_and_path, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (
+            host.to_string(),
+            port.parse()
+                .map_err(|_| anyhow::anyhow!("invalid port in {url:?}"))?,
+        ),
+        None => (authority.to_string(), 80),
+    };
+    Ok(ParsedUrl { host, port, path: path.to_string() })
+}
+
+/// Opens a fresh `TcpStream` per request and speaks just enough HTTP/1.1 to
+/// issue a `GET` and read the response, so every successful probe carries
+/// real [`ConnectionTime`] instead of the `None` a pooled client would give
+/// us; a failed probe carries `None`.
+/// Deliberately minimal: plain HTTP only (no TLS), no redirects — it's meant
+/// to hit a local gateway's own endpoints for load/latency measurement, not
+/// to be a general-purpose HTTP client.
+pub struct HttpProber {
+    target: ParsedUrl,
+}
+
+impl HttpProber {
+    pub fn new(url: impl AsRef<str>) -> anyhow::Result<Self> {
+        Ok(Self { target: parse_http_url(url.as_ref())? })
+    }
+
+    async fn probe_once(&self) -> anyhow::Result<(u16, usize, ConnectionTime)> {
+        let dns_start = Instant::now();
+        let addr = tokio::net::lookup_host((self.target.host.as_str(), self.target.port))
+            .await?
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("no addresses for {}", self.target.host))?;
+        let dns_end = Instant::now();
+
+        let dial_start = Instant::now();
+        let stream = TcpStream::connect(addr).await?;
+        let dial_end = Instant::now();
+
+        let request = format!(
+            "GET {path} HTTP/1.1\r\nHost: {host}\r\nConnection: close\r\nUser-Agent: moltis-bench\r\n\r\n",
+            path = self.target.path,
+            host = self.target.host,
+        );
+        let mut reader = BufReader::new(stream);
+        reader.get_mut().write_all(request.as_bytes()).await?;
+
+        let mut status_line = String::new();
+        reader.read_line(&mut status_line).await?;
+        let status = status_line
+            .split_whitespace()
+            .nth(1)
+            .and_then(|code| code.parse::<u16>().ok())
+            .ok_or_else(|| anyhow::anyhow!("malformed status line: {status_line:?}"))?;
+
+        let mut content_length = None;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).await? == 0 || line == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse::<usize>().ok();
+                }
+            }
+        }
+
+        let mut body = Vec::new();
+        match content_length {
+            Some(len) => {
+                body.resize(len, 0);
+                reader.read_exact(&mut body).await?;
+            }
+            None => {
+                reader.read_to_end(&mut body).await?;
+            }
+        }
+
+        Ok((status, body.len(), ConnectionTime { dns_start, dns_end, dial_start, dial_end }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Prober for HttpProber {
+    async fn probe(&self) -> RequestResult {
+        let start = Instant::now();
+        let (status, body_len, connection_time) = match self.probe_once().await {
+            Ok((status, body_len, connection_time)) => {
+                (Some(status), body_len, Some(connection_time))
+            }
+            Err(_) => (None, 0, None),
+        };
+        RequestResult { start, end: Instant::now(), status, body_len, connection_time }
+    }
+}
+
+/// Stop conditions and concurrency for a bench run: `-z` (duration), `-n`
+/// (request count), `-c` (concurrent workers). When both `duration` and
+/// `count` are set, whichever is reached first ends the run.
+#[derive(Debug, Clone, Copy)]
+pub struct BenchConfig {
+    pub concurrency: usize,
+    pub duration: Option<Duration>,
+    pub count: Option<usize>,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self { concurrency: 1, duration: Some(Duration::from_secs(10)), count: None }
+    }
+}
+
+/// Drive concurrent load through `prober` until a [`BenchConfig`] stop
+/// condition is reached, returning every completed [`RequestResult`].
+pub async fn run_bench(prober: Arc<dyn Prober>, config: BenchConfig) -> Vec<RequestResult> {
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let issued = Arc::new(AtomicUsize::new(0));
+    let run_until = config.duration.map(|d| Instant::now() + d);
+    let concurrency = config.concurrency.max(1);
+
+    let mut workers = Vec::with_capacity(concurrency);
+    for _ in 0..concurrency {
+        let prober = prober.clone();
+        let results = results.clone();
+        let issued = issued.clone();
+        workers.push(tokio::spawn(async move {
+            loop {
+                if let Some(deadline) = run_until {
+                    if Instant::now() >= deadline {
+                        break;
+                    }
+                }
+                if let Some(count) = config.count {
+                    if issued.fetch_add(1, Ordering::SeqCst) >= count {
+                        break;
+                    }
+                } else {
+                    issued.fetch_add(1, Ordering::SeqCst);
+                }
+
+                let result = prober.probe().await;
+                results.lock().await.push(result);
+            }
+        }));
+    }
+
+    for worker in workers {
+        let _ = worker.await;
+    }
+
+    Arc::try_unwrap(results).map(Mutex::into_inner).unwrap_or_default()
+}
+
+/// Latency percentiles, throughput, and a status-code histogram computed
+/// from a completed bench run.
+#[derive(Debug, Clone)]
+pub struct BenchSummary {
+    pub total_requests: usize,
+    pub elapsed: Duration,
+    pub requests_per_sec: f64,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    /// `(status_or_none, count)`, `None` meaning a below-HTTP-layer failure.
+    pub status_histogram: Vec<(Option<u16>, usize)>,
+    /// Connection-establishment percentiles, broken out from `p50`/`p90`/`p99`
+    /// (which measure the full request including any connection setup).
+    /// `None` when no result in the run carried [`ConnectionTime`] (e.g. a
+    /// run in which every [`HttpProber`] probe failed below the HTTP layer).
+    pub connection: Option<ConnectionBreakdown>,
+}
+
+/// Connection-establishment latency percentiles, computed only over the
+/// subset of a run's [`RequestResult`]s that opened a fresh connection
+/// (`connection_time.is_some()`) rather than reusing a pooled one.
+#[derive(Debug, Clone)]
+pub struct ConnectionBreakdown {
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+}
+
+/// Aggregate raw [`RequestResult`]s into a [`BenchSummary`]. `elapsed` is the
+/// caller-measured wall-clock span of the whole run (not derivable from the
+/// individual results alone, since workers run concurrently).
+pub fn summarize(results: &[RequestResult], elapsed: Duration) -> BenchSummary {
+    let mut latencies: Vec<Duration> = results.iter().map(RequestResult::latency).collect();
+    latencies.sort();
+
+    let mut histogram: Vec<(Option<u16>, usize)> = Vec::new();
+    for result in results {
+        match histogram.iter_mut().find(|(status, _)| *status == result.status) {
+            Some((_, count)) => *count += 1,
+            None => histogram.push((result.status, 1)),
+        }
+    }
+
+    let requests_per_sec = if elapsed.as_secs_f64() > 0.0 {
+        results.len() as f64 / elapsed.as_secs_f64()
+    } else {
+        0.0
+    };
+
+    let mut connection_times: Vec<Duration> = results
+        .iter()
+        .filter_map(|r| r.connection_time.map(|c| c.total()))
+        .collect();
+    connection_times.sort();
+    let connection = (!connection_times.is_empty()).then(|| ConnectionBreakdown {
+        p50: percentile(&connection_times, 0.50),
+        p90: percentile(&connection_times, 0.90),
+        p99: percentile(&connection_times, 0.99),
+    });
+
+    BenchSummary {
+        total_requests: results.len(),
+        elapsed,
+        requests_per_sec,
+        p50: percentile(&latencies, 0.50),
+        p90: percentile(&latencies, 0.90),
+        p99: percentile(&latencies, 0.99),
+        status_histogram: histogram,
+        connection,
+    }
+}
+
+/// Nearest-rank percentile over an already-sorted slice. `Duration::ZERO` for
+/// an empty slice.
+fn percentile(sorted: &[Duration], fraction: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = ((sorted.len() as f64) * fraction).ceil() as usize;
+    let index = rank.saturating_sub(1).min(sorted.len() - 1);
+    sorted[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedProber {
+        latency: Duration,
+        status: u16,
+    }
+
+    #[async_trait::async_trait]
+    impl Prober for FixedProber {
+        async fn probe(&self) -> RequestResult {
+            let start = Instant::now();
+            tokio::time::sleep(self.latency).await;
+            RequestResult {
+                start,
+                end: Instant::now(),
+                status: Some(self.status),
+                body_len: 4,
+                connection_time: None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_percentile_of_empty_slice_is_zero() {
+        assert_eq!(percentile(&[], 0.50), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let sorted: Vec<Duration> =
+            (1..=10).map(|ms| Duration::from_millis(ms)).collect();
+        assert_eq!(percentile(&sorted, 0.50), Duration::from_millis(5));
+        assert_eq!(percentile(&sorted, 0.90), Duration::from_millis(9));
+        assert_eq!(percentile(&sorted, 0.99), Duration::from_millis(10));
+    }
+
+    #[tokio::test]
+    async fn test_run_bench_stops_at_request_count() {
+        let prober = Arc::new(FixedProber { latency: Duration::from_millis(1), status: 200 });
+        let config = BenchConfig { concurrency: 4, duration: None, count: Some(20) };
+
+        let results = run_bench(prober, config).await;
+        assert_eq!(results.len(), 20);
+    }
+
+    #[tokio::test]
+    async fn test_run_bench_stops_at_duration() {
+        let prober = Arc::new(FixedProber { latency: Duration::from_millis(5), status: 200 });
+        let config = BenchConfig { concurrency: 2, duration: Some(Duration::from_millis(50)), count: None };
+
+        let start = Instant::now();
+        let results = run_bench(prober, config).await;
+        assert!(!results.is_empty());
+        assert!(start.elapsed() < Duration::from_secs(1), "run_bench should not run past its deadline");
+    }
+
+    #[test]
+    fn test_summarize_computes_percentiles_and_histogram() {
+        let now = Instant::now();
+        let results: Vec<RequestResult> = (1..=10)
+            .map(|ms| RequestResult {
+                start: now,
+                end: now + Duration::from_millis(ms),
+                status: if ms <= 8 { Some(200) } else { Some(500) },
+                body_len: 10,
+                connection_time: None,
+            })
+            .collect();
+
+        let summary = summarize(&results, Duration::from_secs(1));
+        assert_eq!(summary.total_requests, 10);
+        assert_eq!(summary.p50, Duration::from_millis(5));
+        assert_eq!(summary.requests_per_sec, 10.0);
+        assert_eq!(
+            summary.status_histogram.iter().find(|(s, _)| *s == Some(200)).map(|(_, c)| *c),
+            Some(8)
+        );
+        assert_eq!(
+            summary.status_histogram.iter().find(|(s, _)| *s == Some(500)).map(|(_, c)| *c),
+            Some(2)
+        );
+        assert!(summary.connection.is_none(), "no result carried connection timing");
+    }
+
+    #[test]
+    fn test_summarize_breaks_out_connection_time() {
+        let now = Instant::now();
+        let results: Vec<RequestResult> = (1..=10)
+            .map(|ms| RequestResult {
+                start: now,
+                end: now + Duration::from_millis(ms),
+                status: Some(200),
+                body_len: 10,
+                connection_time: Some(ConnectionTime {
+                    dns_start: now,
+                    dns_end: now + Duration::from_millis(1),
+                    dial_start: now + Duration::from_millis(1),
+                    dial_end: now + Duration::from_millis(ms),
+                }),
+            })
+            .collect();
+
+        let summary = summarize(&results, Duration::from_secs(1));
+        let connection = summary.connection.expect("all results carried connection timing");
+        assert_eq!(connection.p50, Duration::from_millis(5));
+        assert_eq!(connection.p90, Duration::from_millis(9));
+        assert_eq!(connection.p99, Duration::from_millis(10));
+    }
+
+    #[test]
+    fn test_summarize_connection_breakdown_ignores_pooled_requests() {
+        let now = Instant::now();
+        let mut results: Vec<RequestResult> = (1..=9)
+            .map(|_| RequestResult {
+                start: now,
+                end: now + Duration::from_millis(1),
+                status: Some(200),
+                body_len: 10,
+                connection_time: None,
+            })
+            .collect();
+        results.push(RequestResult {
+            start: now,
+            end: now + Duration::from_millis(50),
+            status: Some(200),
+            body_len: 10,
+            connection_time: Some(ConnectionTime {
+                dns_start: now,
+                dns_end: now + Duration::from_millis(10),
+                dial_start: now + Duration::from_millis(10),
+                dial_end: now + Duration::from_millis(40),
+            }),
+        });
+
+        let summary = summarize(&results, Duration::from_secs(1));
+        let connection = summary.connection.expect("one result carried connection timing");
+        assert_eq!(connection.p50, Duration::from_millis(40));
+        assert_eq!(connection.p99, Duration::from_millis(40));
+    }
+}
@@ -0,0 +1,225 @@
+//! Trusted-proxy aware client IP resolution.
+//!
+//! When moltis runs behind a reverse proxy or load balancer, the TCP peer
+//! address axum's `ConnectInfo` sees is the proxy, not the real client.
+//! [`resolve_client_ip`] recovers the real address from the `Forwarded`
+//! (RFC 7239) or `X-Forwarded-For` header, but only when the immediate peer
+//! is inside a configured `[server] trusted_proxies` CIDR range — otherwise
+//! those headers are trivially spoofable by the client itself, so the raw
+//! socket peer is returned instead.
+//!
+//! Blocked: there is no middleware calling this. This checkout's gateway
+//! crate has no `server.rs` or `lib.rs` at all — only [`crate::cron`] plus
+//! the other chunk3 modules — so there is no axum router to attach a
+//! `ConnectInfo`-consuming layer to, and no request ever actually has its
+//! client IP resolved by [`resolve_client_ip`] outside this file's own
+//! tests. Treat `resolve_client_ip` and `TrustedProxies` as groundwork for
+//! that middleware, not the behavior the request asked for.
+
+use std::net::IpAddr;
+
+/// One CIDR range from `[server] trusted_proxies`.
+#[derive(Debug, Clone, Copy)]
+pub struct IpCidr {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpCidr {
+    pub fn parse(spec: &str) -> anyhow::Result<Self> {
+        let (addr, len) = spec
+            .split_once('/')
+            .ok_or_else(|| anyhow::anyhow!("expected CIDR notation, e.g. 10.0.0.0/8, got {spec:?}"))?;
+        let network: IpAddr = addr
+            .parse()
+            .map_err(|e| anyhow::anyhow!("invalid address in {spec:?}: {e}"))?;
+        let prefix_len: u8 = len
+            .parse()
+            .map_err(|e| anyhow::anyhow!("invalid prefix length in {spec:?}: {e}"))?;
+
+        let max_len = if network.is_ipv4() { 32 } else { 128 };
+        if prefix_len > max_len {
+            anyhow::bail!("prefix length {prefix_len} exceeds {max_len} for {spec:?}");
+        }
+
+        Ok(Self { network, prefix_len })
+    }
+
+    pub fn contains(&self, addr: &IpAddr) -> bool {
+        match (self.network, addr) {
+            (IpAddr::V4(net), IpAddr::V4(addr)) => {
+                let mask: u32 = if self.prefix_len == 0 { 0 } else { u32::MAX << (32 - self.prefix_len) };
+                (u32::from(net) & mask) == (u32::from(*addr) & mask)
+            }
+            (IpAddr::V6(net), IpAddr::V6(addr)) => {
+                let mask: u128 = if self.prefix_len == 0 { 0 } else { u128::MAX << (128 - self.prefix_len) };
+                (u128::from(net) & mask) == (u128::from(*addr) & mask)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// `[server] trusted_proxies` — reverse-proxy peer ranges allowed to set
+/// `Forwarded`/`X-Forwarded-For`.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(Vec<IpCidr>);
+
+impl TrustedProxies {
+    pub fn parse(specs: &[String]) -> anyhow::Result<Self> {
+        Ok(Self(specs.iter().map(|s| IpCidr::parse(s)).collect::<anyhow::Result<Vec<_>>>()?))
+    }
+
+    pub fn contains(&self, addr: &IpAddr) -> bool {
+        self.0.iter().any(|cidr| cidr.contains(addr))
+    }
+}
+
+/// Resolve the effective client address for a request whose immediate TCP
+/// peer is `peer`. Only consults `forwarded`/`x_forwarded_for` when `peer`
+/// is inside `trusted`, in priority order (RFC 7239 `Forwarded`, then
+/// `X-Forwarded-For`); falls back to `peer` otherwise, or if neither header
+/// parses.
+pub fn resolve_client_ip(
+    peer: IpAddr,
+    forwarded: Option<&str>,
+    x_forwarded_for: Option<&str>,
+    trusted: &TrustedProxies,
+) -> IpAddr {
+    if !trusted.contains(&peer) {
+        return peer;
+    }
+
+    forwarded
+        .and_then(parse_forwarded_for)
+        .or_else(|| x_forwarded_for.and_then(parse_x_forwarded_for))
+        .unwrap_or(peer)
+}
+
+/// Extract the first `for=` address from an RFC 7239 `Forwarded` header,
+/// e.g. `for=192.0.2.60;proto=http;by=203.0.113.43`.
+fn parse_forwarded_for(header: &str) -> Option<IpAddr> {
+    let first_element = header.split(',').next()?;
+    for param in first_element.split(';') {
+        let (key, value) = param.trim().split_once('=')?;
+        if key.trim().eq_ignore_ascii_case("for") {
+            return parse_forwarded_node(value.trim());
+        }
+    }
+    None
+}
+
+/// Parse a `Forwarded: for=...` node value: a bare IPv4 (optionally with a
+/// trailing `:port`), a bracketed IPv6 literal (`"[2001:db8::1]:4711"`), or
+/// either quoted.
+fn parse_forwarded_node(value: &str) -> Option<IpAddr> {
+    let unquoted = value.trim_matches('"');
+    let host = if let Some(rest) = unquoted.strip_prefix('[') {
+        rest.split(']').next()?
+    } else if unquoted.matches(':').count() == 1 {
+        unquoted.split(':').next()?
+    } else {
+        unquoted
+    };
+    host.parse().ok()
+}
+
+/// Extract the first address from a (possibly multi-hop) `X-Forwarded-For`
+/// header: `client, proxy1, proxy2` — the first entry is the original client.
+fn parse_x_forwarded_for(header: &str) -> Option<IpAddr> {
+    header.split(',').next()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ip_cidr_v4_contains() {
+        let cidr = IpCidr::parse("10.0.0.0/8").unwrap();
+        assert!(cidr.contains(&"10.1.2.3".parse().unwrap()));
+        assert!(!cidr.contains(&"11.0.0.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_ip_cidr_v6_contains() {
+        let cidr = IpCidr::parse("2001:db8::/32").unwrap();
+        assert!(cidr.contains(&"2001:db8::1".parse().unwrap()));
+        assert!(!cidr.contains(&"2001:db9::1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_ip_cidr_rejects_prefix_too_wide() {
+        assert!(IpCidr::parse("10.0.0.0/33").is_err());
+    }
+
+    #[test]
+    fn test_ip_cidr_zero_prefix_matches_everything() {
+        let cidr = IpCidr::parse("0.0.0.0/0").unwrap();
+        assert!(cidr.contains(&"203.0.113.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_parse_forwarded_for_basic() {
+        assert_eq!(
+            parse_forwarded_for("for=192.0.2.60;proto=http;by=203.0.113.43"),
+            Some("192.0.2.60".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_forwarded_for_quoted_ipv6_with_port() {
+        assert_eq!(
+            parse_forwarded_for(r#"for="[2001:db8::1]:4711""#),
+            Some("2001:db8::1".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_forwarded_for_multiple_hops_uses_first() {
+        assert_eq!(
+            parse_forwarded_for("for=192.0.2.60, for=198.51.100.1"),
+            Some("192.0.2.60".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_x_forwarded_for_uses_first_entry() {
+        assert_eq!(
+            parse_x_forwarded_for("203.0.113.1, 10.0.0.1, 10.0.0.2"),
+            Some("203.0.113.1".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_resolve_uses_header_only_when_peer_trusted() {
+        let trusted = TrustedProxies::parse(&["10.0.0.0/8".to_string()]).unwrap();
+        let peer: IpAddr = "10.0.0.1".parse().unwrap();
+
+        let resolved = resolve_client_ip(peer, None, Some("203.0.113.1"), &trusted);
+        assert_eq!(resolved, "203.0.113.1".parse::<IpAddr>().unwrap());
+    }
+
+    #[test]
+    fn test_resolve_falls_back_to_peer_when_untrusted() {
+        let trusted = TrustedProxies::parse(&["10.0.0.0/8".to_string()]).unwrap();
+        let peer: IpAddr = "203.0.113.50".parse().unwrap();
+
+        let resolved = resolve_client_ip(peer, None, Some("198.51.100.1"), &trusted);
+        assert_eq!(resolved, peer, "untrusted peer's forwarded header must be ignored");
+    }
+
+    #[test]
+    fn test_resolve_prefers_forwarded_over_x_forwarded_for() {
+        let trusted = TrustedProxies::parse(&["10.0.0.0/8".to_string()]).unwrap();
+        let peer: IpAddr = "10.0.0.1".parse().unwrap();
+
+        let resolved = resolve_client_ip(
+            peer,
+            Some("for=192.0.2.60"),
+            Some("198.51.100.1"),
+            &trusted,
+        );
+        assert_eq!(resolved, "192.0.2.60".parse::<IpAddr>().unwrap());
+    }
+}
@@ -96,9 +96,15 @@ impl CronServiceTrait for LiveCronService {
             .and_then(|v| v.as_str())
             .ok_or_else(|| "missing 'id'".to_string())?;
         let limit = params.get("limit").and_then(|v| v.as_u64()).unwrap_or(20) as usize;
+        let status_filter = params
+            .get("status")
+            .and_then(|v| v.as_str())
+            .map(str::parse)
+            .transpose()
+            .map_err(|e: anyhow::Error| format!("invalid 'status': {e}"))?;
         let runs = self
             .inner
-            .runs(id, limit)
+            .runs(id, limit, status_filter)
             .await
             .map_err(|e| e.to_string())?;
         serde_json::to_value(runs).map_err(|e| e.to_string())
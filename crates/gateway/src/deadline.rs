@@ -0,0 +1,155 @@
+//! Per-request deadline propagation.
+//!
+//! Callers can declare how long they're willing to wait via an
+//! `X-Request-Timeout` header (e.g. `30s`, `500ms`); the gateway always
+//! honors the *shorter* of that and its own configured default, so a client
+//! can tighten its budget but never loosen the server's protection against
+//! slow or forgotten timeouts. [`with_deadline`] wraps a handler future in
+//! `tokio::time::timeout` so that on expiry the future is dropped — and with
+//! it any in-flight downstream provider call — rather than left to run to
+//! completion after the client has given up.
+//!
+//! Blocked: no handler is actually wrapped in this. This checkout's gateway
+//! crate has no `server.rs` or `lib.rs` at all — only [`crate::cron`] plus
+//! the other chunk3 modules — so there is no axum router to add timeout
+//! middleware to, and the `X-Request-Timeout` header is never read from a
+//! real request outside this file's own tests. [`effective_deadline`] and
+//! [`with_deadline`] are correct in isolation but don't enforce anything on
+//! a running gateway yet.
+
+use std::{future::Future, time::Duration};
+
+/// Header a client uses to declare how long it's willing to wait for a
+/// response, e.g. `X-Request-Timeout: 30s`.
+pub const REQUEST_TIMEOUT_HEADER: &str = "x-request-timeout";
+
+/// The in-flight future was dropped because `with_deadline`'s timeout
+/// elapsed before it completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeadlineExceeded;
+
+/// Parse an `X-Request-Timeout` value into a [`Duration`].
+///
+/// Accepts a bare integer (seconds) or a number suffixed with `ms`, `s`,
+/// `m`, or `h` — e.g. `"30"`, `"30s"`, `"500ms"`, `"2m"`. Returns `None` for
+/// anything else, including a zero or negative duration, which callers
+/// should treat the same as a missing header rather than an instant timeout.
+pub fn parse_client_timeout(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (digits, unit) = match value.find(|c: char| !c.is_ascii_digit() && c != '.') {
+        Some(idx) => value.split_at(idx),
+        None => (value, "s"),
+    };
+    if digits.is_empty() {
+        return None;
+    }
+    let amount: f64 = digits.parse().ok()?;
+    if amount <= 0.0 {
+        return None;
+    }
+
+    let millis = match unit {
+        "ms" => amount,
+        "s" | "" => amount * 1_000.0,
+        "m" => amount * 60_000.0,
+        "h" => amount * 3_600_000.0,
+        _ => return None,
+    };
+    Some(Duration::from_millis(millis as u64))
+}
+
+/// The deadline actually applied to a request: the server's configured
+/// default, tightened to `client_requested` when the client asked for
+/// something shorter. A client cannot loosen the server's own ceiling.
+pub fn effective_deadline(server_default: Duration, client_requested: Option<Duration>) -> Duration {
+    match client_requested {
+        Some(requested) => server_default.min(requested),
+        None => server_default,
+    }
+}
+
+/// Run `fut` under `deadline`, dropping it (and cancelling anything it was
+/// awaiting, such as a downstream provider call) if the deadline elapses
+/// first.
+pub async fn with_deadline<F: Future>(deadline: Duration, fut: F) -> Result<F::Output, DeadlineExceeded> {
+    tokio::time::timeout(deadline, fut).await.map_err(|_| DeadlineExceeded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bare_seconds() {
+        assert_eq!(parse_client_timeout("30"), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_parse_seconds_suffix() {
+        assert_eq!(parse_client_timeout("30s"), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_parse_millis_suffix() {
+        assert_eq!(parse_client_timeout("500ms"), Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_parse_minutes_and_hours() {
+        assert_eq!(parse_client_timeout("2m"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_client_timeout("1h"), Some(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_parse_fractional_seconds() {
+        assert_eq!(parse_client_timeout("1.5s"), Some(Duration::from_millis(1500)));
+    }
+
+    #[test]
+    fn test_parse_rejects_garbage_and_nonpositive() {
+        assert_eq!(parse_client_timeout("banana"), None);
+        assert_eq!(parse_client_timeout("0s"), None);
+        assert_eq!(parse_client_timeout("-5s"), None);
+        assert_eq!(parse_client_timeout(""), None);
+    }
+
+    #[test]
+    fn test_effective_deadline_prefers_shorter_client_request() {
+        let server_default = Duration::from_secs(30);
+        assert_eq!(
+            effective_deadline(server_default, Some(Duration::from_secs(5))),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn test_effective_deadline_cannot_exceed_server_default() {
+        let server_default = Duration::from_secs(30);
+        assert_eq!(
+            effective_deadline(server_default, Some(Duration::from_secs(300))),
+            server_default
+        );
+    }
+
+    #[test]
+    fn test_effective_deadline_falls_back_to_server_default_when_absent() {
+        let server_default = Duration::from_secs(30);
+        assert_eq!(effective_deadline(server_default, None), server_default);
+    }
+
+    #[tokio::test]
+    async fn test_with_deadline_returns_ok_when_fut_finishes_in_time() {
+        let result = with_deadline(Duration::from_millis(200), async { 42 }).await;
+        assert_eq!(result, Ok(42));
+    }
+
+    #[tokio::test]
+    async fn test_with_deadline_cancels_slow_future() {
+        let result = with_deadline(Duration::from_millis(10), async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            42
+        })
+        .await;
+        assert_eq!(result, Err(DeadlineExceeded));
+    }
+}
@@ -0,0 +1,353 @@
+//! Connect-or-spawn client: find a running gateway under a data directory,
+//! or start one.
+//!
+//! When the gateway boots it publishes its bound address (and PID) to a
+//! lockfile-adjacent JSON file under the data dir via [`publish_address`].
+//! [`connect_or_spawn`] reads that file and probes `/health`; if it's
+//! missing, stale, or unhealthy, it races other callers for an exclusive
+//! [`acquire_lock`] on the same directory. The winner spawns a fresh
+//! gateway and publishes its address; everyone else falls through to
+//! [`wait_for_published_address`], polling with backoff until the winner's
+//! address appears and answers healthy or `deadline` elapses. This lets an
+//! embedder call `connect_or_spawn` without caring whether a gateway is
+//! already running — at most one gets spawned per data dir.
+//!
+//! Note: this checkout's gateway crate has no `server.rs`/`main.rs`
+//! exposing a real `start_gateway` to call, only `cron.rs` plus the other
+//! chunk3 modules, so [`connect_or_spawn`] takes the spawn step as a
+//! closure rather than calling `start_gateway` directly — once it's
+//! restored, the caller passes `|data_dir| start_gateway(..., data_dir)` as
+//! that closure and nothing else here changes.
+
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Contents of the address file a running gateway publishes under its data
+/// dir, read by other processes wanting to connect instead of spawning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GatewayAddress {
+    pub addr: SocketAddr,
+    pub pid: u32,
+}
+
+fn address_file(data_dir: &Path) -> PathBuf {
+    data_dir.join("gateway.addr.json")
+}
+
+fn lock_file(data_dir: &Path) -> PathBuf {
+    data_dir.join("gateway.lock")
+}
+
+/// Write the address file. Overwrites whatever was there, since only the
+/// lock holder calls this.
+pub fn publish_address(data_dir: &Path, addr: SocketAddr) -> anyhow::Result<()> {
+    std::fs::create_dir_all(data_dir)?;
+    let published = GatewayAddress { addr, pid: std::process::id() };
+    std::fs::write(address_file(data_dir), serde_json::to_vec(&published)?)?;
+    Ok(())
+}
+
+/// Read the published address file, if present and parseable. A missing or
+/// corrupt file is treated as "no gateway known" rather than an error.
+pub fn read_published_address(data_dir: &Path) -> Option<GatewayAddress> {
+    let bytes = std::fs::read(address_file(data_dir)).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Exclusive ownership of `data_dir`'s spawn lock, released on drop. Backed
+/// by atomic exclusive file creation rather than flock/fcntl so it needs no
+/// platform-specific locking dependency; only the process that successfully
+/// creates the lock file may proceed to spawn. The file's contents are the
+/// holder's PID, so a lock left behind by a killed process (SIGKILL,
+/// OOM-kill, power loss — none of which run `Drop`) can be told apart from
+/// one a live process still holds.
+pub struct LockGuard {
+    path: PathBuf,
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Try to become the spawner for `data_dir`. `Err` means another process
+/// already holds the lock and is still alive — the caller should fall
+/// through to [`wait_for_published_address`] instead. A lock file whose
+/// recorded PID is no longer running is treated as abandoned and reclaimed,
+/// so a killed holder can't permanently brick `connect_or_spawn` for this
+/// data dir.
+pub fn acquire_lock(data_dir: &Path) -> std::io::Result<LockGuard> {
+    std::fs::create_dir_all(data_dir)?;
+    let path = lock_file(data_dir);
+
+    match create_lock_file(&path) {
+        Ok(()) => return Ok(LockGuard { path }),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e),
+    }
+
+    if !lock_holder_is_alive(&path) {
+        let _ = std::fs::remove_file(&path);
+        create_lock_file(&path)?;
+        return Ok(LockGuard { path });
+    }
+
+    Err(std::io::Error::new(std::io::ErrorKind::AlreadyExists, "gateway spawn lock held by a live process"))
+}
+
+fn create_lock_file(path: &Path) -> std::io::Result<()> {
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    file.write_all(&std::process::id().to_le_bytes())?;
+    Ok(())
+}
+
+/// Whether the process whose PID is recorded in `path` is still alive. A
+/// lock file that can't be read or doesn't hold a parseable PID is treated
+/// as held by a live process — i.e. not reclaimed — since we'd rather wait
+/// out a lock we can't account for than risk two spawners racing.
+fn lock_holder_is_alive(path: &Path) -> bool {
+    match std::fs::read(path) {
+        Ok(bytes) if bytes.len() == 4 => {
+            let pid = u32::from_le_bytes(bytes.try_into().unwrap());
+            is_process_alive(pid)
+        }
+        _ => true,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn is_process_alive(pid: u32) -> bool {
+    Path::new(&format!("/proc/{pid}")).exists()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_process_alive(_pid: u32) -> bool {
+    // No portable liveness check outside /proc; assume alive rather than
+    // risk reclaiming a lock that's still legitimately held.
+    true
+}
+
+/// `GET /health` against `addr` with a short per-attempt timeout. `false` on
+/// any connection error, timeout, or non-success status.
+pub async fn probe_health(addr: SocketAddr, timeout: Duration) -> bool {
+    let client = match reqwest::Client::builder().timeout(timeout).build() {
+        Ok(client) => client,
+        Err(_) => return false,
+    };
+    client
+        .get(format!("http://{addr}/health"))
+        .send()
+        .await
+        .map(|response| response.status().is_success())
+        .unwrap_or(false)
+}
+
+/// Poll for a healthy published address until `deadline` elapses, backing
+/// off between attempts. Used by callers that lost the spawn-lock race.
+async fn wait_for_published_address(
+    data_dir: &Path,
+    deadline: Duration,
+    probe_timeout: Duration,
+) -> anyhow::Result<SocketAddr> {
+    let start = std::time::Instant::now();
+    let mut backoff = Duration::from_millis(20);
+
+    while start.elapsed() < deadline {
+        if let Some(published) = read_published_address(data_dir) {
+            if probe_health(published.addr, probe_timeout).await {
+                return Ok(published.addr);
+            }
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(Duration::from_millis(500));
+    }
+
+    anyhow::bail!("no gateway became healthy under {data_dir:?} within {deadline:?}")
+}
+
+/// Locate a running gateway under `data_dir`, or spawn one.
+///
+/// Checks the published address first; if it's missing or unhealthy, races
+/// for the spawn lock. The winner calls `spawn` (typically wrapping
+/// `start_gateway`), publishes the resulting address, and returns it.
+/// Everyone else waits on [`wait_for_published_address`] for the winner to
+/// publish, up to `deadline`.
+pub async fn connect_or_spawn<F, Fut>(
+    data_dir: &Path,
+    deadline: Duration,
+    spawn: F,
+) -> anyhow::Result<SocketAddr>
+where
+    F: FnOnce(PathBuf) -> Fut,
+    Fut: std::future::Future<Output = anyhow::Result<SocketAddr>>,
+{
+    let probe_timeout = Duration::from_millis(300);
+
+    if let Some(published) = read_published_address(data_dir) {
+        if probe_health(published.addr, probe_timeout).await {
+            return Ok(published.addr);
+        }
+    }
+
+    match acquire_lock(data_dir) {
+        Ok(_guard) => {
+            let addr = spawn(data_dir.to_path_buf()).await?;
+            publish_address(data_dir, addr)?;
+            Ok(addr)
+        }
+        Err(_) => wait_for_published_address(data_dir, deadline, probe_timeout).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    /// A minimal TCP responder that answers any request with `200 OK`,
+    /// standing in for a real gateway's `/health` endpoint.
+    async fn spawn_stub_server() -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else { break };
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let _ = socket.read(&mut buf).await;
+                    let _ = socket.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").await;
+                });
+            }
+        });
+        addr
+    }
+
+    #[test]
+    fn test_publish_and_read_address_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let addr: SocketAddr = "127.0.0.1:4321".parse().unwrap();
+
+        publish_address(dir.path(), addr).unwrap();
+        let published = read_published_address(dir.path()).unwrap();
+
+        assert_eq!(published.addr, addr);
+        assert_eq!(published.pid, std::process::id());
+    }
+
+    #[test]
+    fn test_read_published_address_missing_file_is_none() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_published_address(dir.path()).is_none());
+    }
+
+    #[test]
+    fn test_acquire_lock_is_exclusive() {
+        let dir = tempfile::tempdir().unwrap();
+        let _first = acquire_lock(dir.path()).unwrap();
+        assert!(acquire_lock(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_acquire_lock_reclaims_lock_from_dead_process() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // A process that has already exited -- its PID is guaranteed dead
+        // by the time `wait` returns.
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let dead_pid = child.id();
+        child.wait().unwrap();
+
+        std::fs::create_dir_all(dir.path()).unwrap();
+        std::fs::write(lock_file(dir.path()), dead_pid.to_le_bytes()).unwrap();
+
+        assert!(acquire_lock(dir.path()).is_ok(), "a lock held by a dead process should be reclaimed");
+    }
+
+    #[test]
+    fn test_lock_releases_on_drop() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let _guard = acquire_lock(dir.path()).unwrap();
+        }
+        assert!(acquire_lock(dir.path()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_probe_health_true_for_responsive_server_false_otherwise() {
+        let addr = spawn_stub_server().await;
+        assert!(probe_health(addr, Duration::from_millis(200)).await);
+
+        let unbound: SocketAddr = "127.0.0.1:1".parse().unwrap();
+        assert!(!probe_health(unbound, Duration::from_millis(200)).await);
+    }
+
+    #[tokio::test]
+    async fn test_connect_or_spawn_spawns_when_nothing_published() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let addr =
+            connect_or_spawn(dir.path(), Duration::from_secs(2), |_| async move { Ok(spawn_stub_server().await) })
+                .await
+                .unwrap();
+
+        assert!(probe_health(addr, Duration::from_millis(200)).await);
+        assert_eq!(read_published_address(dir.path()).unwrap().addr, addr);
+    }
+
+    #[tokio::test]
+    async fn test_connect_or_spawn_reuses_healthy_published_address() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = spawn_stub_server().await;
+        publish_address(dir.path(), existing).unwrap();
+
+        let spawn_called = Arc::new(AtomicBool::new(false));
+        let flag = spawn_called.clone();
+        let resolved = connect_or_spawn(dir.path(), Duration::from_secs(2), move |_| {
+            let flag = flag.clone();
+            async move {
+                flag.store(true, Ordering::SeqCst);
+                Ok(spawn_stub_server().await)
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(resolved, existing);
+        assert!(!spawn_called.load(Ordering::SeqCst), "must not spawn when an existing gateway is healthy");
+    }
+
+    #[tokio::test]
+    async fn test_connect_or_spawn_waits_for_concurrent_winner() {
+        let dir = tempfile::tempdir().unwrap();
+        let winner_addr = spawn_stub_server().await;
+
+        // Simulate a concurrent caller that already won the lock race.
+        let _lock = acquire_lock(dir.path()).unwrap();
+        let dir_path = dir.path().to_path_buf();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            publish_address(&dir_path, winner_addr).unwrap();
+        });
+
+        let resolved =
+            connect_or_spawn(dir.path(), Duration::from_secs(2), |_| async move { Ok(spawn_stub_server().await) })
+                .await
+                .unwrap();
+
+        assert_eq!(resolved, winner_addr);
+    }
+}
@@ -0,0 +1,187 @@
+//! Bootstrap readiness tracking for `start_gateway`.
+//!
+//! This is distinct from liveness: `/health` answers as soon as the HTTP
+//! listener is up, `/ready` should only answer 200 once the gateway can
+//! actually serve requests — migrations applied, providers reachable, TLS
+//! certs loaded. [`Readiness`] tracks each subsystem's progress as a
+//! fraction in `[0.0, 1.0]` and combines them into a single weighted
+//! bootstrap fraction for the `/ready` response body.
+//!
+//! Blocked: there is no `/ready` route. This checkout's gateway crate has no
+//! `server.rs` or `lib.rs` at all — only [`crate::cron`] plus the other
+//! chunk3 modules — so there is no router to add the route to and no
+//! `start_gateway` to construct a shared `Readiness` inside of. [`Readiness`]
+//! compiles and is unit tested on its own, but nothing in this tree calls
+//! [`Readiness::report`] or [`Readiness::ready_for_traffic`] outside its own
+//! tests; treat this as scaffolding for the route, not the route itself.
+
+/// Weight of the TCP listener + connection setup in the overall readiness
+/// fraction (see [`Readiness::report`]).
+const W_CONN: f64 = 0.15;
+/// Weight of SQLite migrations.
+const W_DB: f64 = 0.25;
+/// Weight of provider connectivity — the dominant cost, since most boot
+/// time is spent waiting on upstream provider handshakes.
+const W_PROVIDERS: f64 = 0.60;
+
+/// Bootstrap fraction above which the gateway is considered ready, once the
+/// critical subsystems (listener, DB) have also fully completed.
+const READY_THRESHOLD: f64 = 0.999;
+
+/// Progress of the TCP listener, independent of anything it serves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConnStatus {
+    #[default]
+    NotStarted,
+    Listening,
+}
+
+impl ConnStatus {
+    pub fn frac(self) -> f64 {
+        match self {
+            ConnStatus::NotStarted => 0.0,
+            ConnStatus::Listening => 1.0,
+        }
+    }
+}
+
+/// Progress of SQLite schema migrations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DbStatus {
+    #[default]
+    NotStarted,
+    Migrating,
+    Ready,
+}
+
+impl DbStatus {
+    pub fn frac(self) -> f64 {
+        match self {
+            DbStatus::NotStarted => 0.0,
+            DbStatus::Migrating => 0.5,
+            DbStatus::Ready => 1.0,
+        }
+    }
+}
+
+/// Progress of upstream provider connectivity: `connected` of `total`
+/// configured providers have completed their handshake. A gateway with no
+/// providers configured reports fully ready on this axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ProviderStatus {
+    pub connected: usize,
+    pub total: usize,
+}
+
+impl ProviderStatus {
+    pub fn frac(self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.connected as f64 / self.total as f64
+        }
+    }
+}
+
+/// Snapshot of bootstrap progress, served as the `/ready` response body.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct ReadinessReport {
+    pub fraction: f64,
+    pub conn: f64,
+    pub db: f64,
+    pub providers: f64,
+    pub ready: bool,
+}
+
+/// Tracks per-subsystem bootstrap progress and computes the weighted
+/// fraction + `ready` boolean for `/ready`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Readiness {
+    pub conn: ConnStatus,
+    pub db: DbStatus,
+    pub providers: ProviderStatus,
+}
+
+impl Readiness {
+    /// Weighted bootstrap fraction and whether the gateway is ready for
+    /// traffic: the fraction has crossed [`READY_THRESHOLD`] and both
+    /// critical subsystems (listener, DB) have fully completed. Providers
+    /// aren't checked by status the way the listener and DB are, but their
+    /// weight keeps the fraction below the threshold while any configured
+    /// provider is still handshaking (3 of 5 connected gives 0.76).
+    pub fn report(&self) -> ReadinessReport {
+        let conn = self.conn.frac();
+        let db = self.db.frac();
+        let providers = self.providers.frac();
+        let fraction = conn * W_CONN + db * W_DB + providers * W_PROVIDERS;
+        let ready =
+            fraction >= READY_THRESHOLD && self.conn == ConnStatus::Listening && self.db == DbStatus::Ready;
+
+        ReadinessReport { fraction, conn, db, providers, ready }
+    }
+
+    /// `true` once `/ready` should return 200 instead of 503.
+    pub fn ready_for_traffic(&self) -> bool {
+        self.report().ready
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weights_sum_to_one() {
+        assert!((W_CONN + W_DB + W_PROVIDERS - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_not_started_is_zero_fraction_and_not_ready() {
+        let readiness = Readiness::default();
+        let report = readiness.report();
+        assert_eq!(report.fraction, 0.0);
+        assert!(!report.ready);
+    }
+
+    #[test]
+    fn test_partial_provider_connectivity_is_weighted_fraction() {
+        let readiness = Readiness {
+            conn: ConnStatus::Listening,
+            db: DbStatus::Ready,
+            providers: ProviderStatus { connected: 3, total: 5 },
+        };
+        let report = readiness.report();
+        let expected = 1.0 * W_CONN + 1.0 * W_DB + 0.6 * W_PROVIDERS;
+        assert!((report.fraction - expected).abs() < 1e-9);
+        assert!(!report.ready, "partial provider connectivity should not be ready");
+    }
+
+    #[test]
+    fn test_fully_complete_is_ready() {
+        let readiness = Readiness {
+            conn: ConnStatus::Listening,
+            db: DbStatus::Ready,
+            providers: ProviderStatus { connected: 2, total: 2 },
+        };
+        assert!(readiness.ready_for_traffic());
+    }
+
+    #[test]
+    fn test_no_providers_configured_counts_as_complete_on_that_axis() {
+        let status = ProviderStatus::default();
+        assert_eq!(status.frac(), 1.0);
+    }
+
+    #[test]
+    fn test_db_migrating_is_not_ready_even_if_fraction_is_high() {
+        let readiness = Readiness {
+            conn: ConnStatus::Listening,
+            db: DbStatus::Migrating,
+            providers: ProviderStatus { connected: 10, total: 10 },
+        };
+        assert!(
+            !readiness.ready_for_traffic(),
+            "DB must fully complete, not just contribute a high fraction"
+        );
+    }
+}
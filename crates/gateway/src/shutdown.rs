@@ -0,0 +1,233 @@
+//! Graceful shutdown with connection draining.
+//!
+//! `boot_time.rs` currently stops the gateway task with `JoinHandle::abort`,
+//! which is fine for a test but has no equivalent for a real deploy: it
+//! drops in-flight requests and truncates provider streams rather than
+//! letting them finish. This module provides the pieces a `spawn_gateway` /
+//! `GatewayHandle` pair would use instead: a [`ShutdownSignal`] that a
+//! SIGINT/SIGTERM listener or a caller can trigger, an [`InFlightTracker`]
+//! that the request-handling loop marks one request at a time, and
+//! [`drain`], which waits for that count to reach zero up to a configurable
+//! timeout before giving up.
+//!
+//! Blocked: no accept loop uses any of this. This checkout's gateway crate
+//! has no `server.rs` or `main.rs` at all — only `cron.rs` plus the other
+//! chunk3 modules — so there's no `start_gateway` to turn into
+//! `spawn_gateway` + `GatewayHandle`, and the `tests/boot_time.rs`
+//! integration test still tears the gateway task down with
+//! `JoinHandle::abort`, the exact behavior this module exists to replace.
+//! [`ShutdownSignal`], [`InFlightTracker`], and [`GatewayHandle`] are
+//! exercised only by this file's own tests, not by a real accept loop.
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use tokio::sync::watch;
+
+/// Triggers shutdown for every [`ShutdownListener`] cloned from the same
+/// signal.
+#[derive(Clone)]
+pub struct ShutdownSignal {
+    tx: watch::Sender<bool>,
+}
+
+/// Receives the shutdown notification triggered by a [`ShutdownSignal`].
+#[derive(Clone)]
+pub struct ShutdownListener {
+    rx: watch::Receiver<bool>,
+}
+
+impl ShutdownSignal {
+    /// A fresh, untriggered signal and its first listener. Clone the
+    /// listener for every accept-loop iteration that needs to race against
+    /// shutdown.
+    pub fn new() -> (Self, ShutdownListener) {
+        let (tx, rx) = watch::channel(false);
+        (Self { tx }, ShutdownListener { rx })
+    }
+
+    /// Notify every listener that the gateway should stop accepting new
+    /// connections. Idempotent: triggering twice is a no-op the second time.
+    pub fn trigger(&self) {
+        let _ = self.tx.send(true);
+    }
+}
+
+impl ShutdownListener {
+    /// Resolve once [`ShutdownSignal::trigger`] has been called. Resolves
+    /// immediately if it already has been.
+    pub async fn recv(&mut self) {
+        if *self.rx.borrow() {
+            return;
+        }
+        let _ = self.rx.changed().await;
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        *self.rx.borrow()
+    }
+}
+
+/// Counts requests currently being handled, so [`drain`] knows when it's
+/// safe to return.
+#[derive(Clone, Default)]
+pub struct InFlightTracker(Arc<AtomicUsize>);
+
+/// RAII guard marking one request as in flight; decrements the tracker's
+/// count on drop, including on panic or early return from the handler.
+pub struct InFlightGuard(Arc<AtomicUsize>);
+
+impl InFlightTracker {
+    /// Mark one request as started; drop the returned guard when it finishes.
+    pub fn track(&self) -> InFlightGuard {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard(self.0.clone())
+    }
+
+    pub fn count(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Wait for `tracker`'s in-flight count to reach zero, polling at a short
+/// fixed interval. Returns `true` if it drained before `timeout` elapsed,
+/// `false` if requests were still in flight when the deadline hit — callers
+/// should log and proceed with shutdown regardless, since waiting forever
+/// for a stuck request would block a redeploy indefinitely.
+pub async fn drain(tracker: &InFlightTracker, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    while tracker.count() > 0 {
+        if Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    true
+}
+
+/// Wait for an OS interrupt/terminate signal (SIGINT, or SIGTERM on Unix).
+/// Only waits: it does not call [`ShutdownSignal::trigger`] itself.
+pub async fn wait_for_os_signal() {
+    #[cfg(unix)]
+    {
+        let mut terminate = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler");
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            _ = terminate.recv() => {}
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+/// A non-blocking gateway task plus the means to stop it cleanly. Returned
+/// by the `spawn_gateway` variant of `start_gateway` once the accept loop
+/// exists; tests and embedders call [`GatewayHandle::shutdown`] instead of
+/// `JoinHandle::abort`.
+pub struct GatewayHandle {
+    signal: ShutdownSignal,
+    join: tokio::task::JoinHandle<anyhow::Result<()>>,
+}
+
+impl GatewayHandle {
+    pub fn new(signal: ShutdownSignal, join: tokio::task::JoinHandle<anyhow::Result<()>>) -> Self {
+        Self { signal, join }
+    }
+
+    /// Trigger shutdown and wait for the gateway task to finish draining
+    /// and return.
+    pub async fn shutdown(self) -> anyhow::Result<()> {
+        self.signal.trigger();
+        self.join.await?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_listener_resolves_after_trigger() {
+        let (signal, mut listener) = ShutdownSignal::new();
+        assert!(!listener.is_triggered());
+
+        signal.trigger();
+        listener.recv().await;
+        assert!(listener.is_triggered());
+    }
+
+    #[tokio::test]
+    async fn test_listener_already_triggered_resolves_immediately() {
+        let (signal, listener) = ShutdownSignal::new();
+        signal.trigger();
+
+        let mut listener = listener;
+        tokio::time::timeout(Duration::from_millis(50), listener.recv())
+            .await
+            .expect("recv should resolve immediately when already triggered");
+    }
+
+    #[test]
+    fn test_in_flight_tracker_counts_guards() {
+        let tracker = InFlightTracker::default();
+        assert_eq!(tracker.count(), 0);
+
+        let guard_a = tracker.track();
+        let guard_b = tracker.track();
+        assert_eq!(tracker.count(), 2);
+
+        drop(guard_a);
+        assert_eq!(tracker.count(), 1);
+
+        drop(guard_b);
+        assert_eq!(tracker.count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_drain_succeeds_once_guards_drop() {
+        let tracker = InFlightTracker::default();
+        let guard = tracker.track();
+
+        let tracker_clone = tracker.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            drop(guard);
+        });
+
+        assert!(drain(&tracker_clone, Duration::from_secs(1)).await);
+    }
+
+    #[tokio::test]
+    async fn test_drain_times_out_on_stuck_request() {
+        let tracker = InFlightTracker::default();
+        let _guard = tracker.track();
+
+        assert!(!drain(&tracker, Duration::from_millis(20)).await);
+    }
+
+    #[tokio::test]
+    async fn test_gateway_handle_shutdown_triggers_and_awaits() {
+        let (signal, mut listener) = ShutdownSignal::new();
+        let join = tokio::spawn(async move {
+            listener.recv().await;
+            Ok(())
+        });
+        let handle = GatewayHandle::new(signal, join);
+
+        handle.shutdown().await.unwrap();
+    }
+}
@@ -0,0 +1,97 @@
+//! Configuration for [`crate::manager::MemoryManager`].
+
+use std::path::PathBuf;
+
+/// When to include `path#start_line` citations in returned text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CitationMode {
+    Always,
+    Never,
+    /// Only cite when a result set draws from more than one source file,
+    /// since a single-source answer reads fine without one.
+    #[default]
+    WhenMultipleSources,
+}
+
+/// Tuning for the typo-tolerant keyword matcher (see [`crate::search`]).
+#[derive(Debug, Clone, Copy)]
+pub struct TypoTolerance {
+    /// Query terms of this length or shorter require an exact match.
+    pub exact_below_len: usize,
+    /// Query terms up to this length tolerate a Levenshtein distance of 1.
+    pub one_edit_below_len: usize,
+    /// Maximum Levenshtein distance considered a match at all, regardless of
+    /// term length (terms longer than `one_edit_below_len` get this).
+    pub max_edit_distance: usize,
+    /// Cap on the number of stored terms compared per query term, after the
+    /// cheap length/first-character prefilter, to bound worst-case cost.
+    pub max_candidates_per_term: usize,
+}
+
+impl Default for TypoTolerance {
+    fn default() -> Self {
+        Self {
+            exact_below_len: 3,
+            one_edit_below_len: 7,
+            max_edit_distance: 2,
+            max_candidates_per_term: 64,
+        }
+    }
+}
+
+/// Configuration for [`crate::manager::MemoryManager`].
+#[derive(Debug, Clone)]
+pub struct MemoryConfig {
+    /// SQLite database path, or `:memory:` for an ephemeral store.
+    pub db_path: String,
+    /// Postgres connection string; when set, selects the pgvector-backed
+    /// store instead of SQLite (see [`crate::store_postgres`]).
+    pub database_url: Option<String>,
+    /// Root directory for `MEMORY.md` and relative memory file resolution.
+    /// Defaults to the current working directory when `None`.
+    pub data_dir: Option<PathBuf>,
+    /// Paths watched for content to index: `MEMORY.md`, `memory/`, daily logs, etc.
+    pub memory_dirs: Vec<PathBuf>,
+    pub chunk_size: usize,
+    pub chunk_overlap: usize,
+    pub vector_weight: f32,
+    pub keyword_weight: f32,
+    pub citation_mode: CitationMode,
+    pub typo_tolerance: TypoTolerance,
+    /// File extensions (without the leading dot) eligible for
+    /// [`crate::manager::MemoryManager::crawl`] when `all_files` is false.
+    pub crawl_extensions: Vec<String>,
+    /// Hex-encoded 32-byte ed25519 seed. When set, the chunk manifest is
+    /// signed on every write (see [`crate::integrity`]) so `verify_store`
+    /// can later check it came from this manager.
+    pub manifest_signing_key: Option<String>,
+    /// Hex-encoded 32-byte ed25519 public key. When set,
+    /// [`crate::manager::MemoryManager::verify_store`] requires the on-disk
+    /// manifest to carry a valid signature from this key before trusting it.
+    pub manifest_public_key: Option<String>,
+}
+
+impl Default for MemoryConfig {
+    fn default() -> Self {
+        Self {
+            db_path: ":memory:".into(),
+            database_url: None,
+            data_dir: None,
+            memory_dirs: Vec::new(),
+            chunk_size: 500,
+            chunk_overlap: 50,
+            vector_weight: 0.7,
+            keyword_weight: 0.3,
+            citation_mode: CitationMode::default(),
+            typo_tolerance: TypoTolerance::default(),
+            crawl_extensions: [
+                "md", "txt", "rs", "py", "js", "ts", "go", "json", "yaml", "yml", "toml",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            manifest_signing_key: None,
+            manifest_public_key: None,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! Embedding provider abstraction, so the manager doesn't care whether
+//! vectors come from a local model, a hosted API, or (in tests) a mock.
+
+use async_trait::async_trait;
+
+/// Produces an embedding vector for a piece of text.
+#[async_trait]
+pub trait EmbeddingProvider: Send + Sync {
+    async fn embed(&self, text: &str) -> anyhow::Result<Vec<f32>>;
+
+    /// Identifier of the underlying model, stored alongside chunks so a
+    /// later model change can be detected and trigger a re-embed.
+    fn model_name(&self) -> &str;
+
+    /// Dimensionality of vectors returned by [`Self::embed`].
+    fn dimensions(&self) -> usize;
+
+    /// Short key identifying the provider (e.g. `"openai"`, `"mock"`), used
+    /// to pick the right client at startup.
+    fn provider_key(&self) -> &str;
+}
@@ -0,0 +1,71 @@
+//! Boilerplate-stripping HTML-to-text extraction, used by
+//! [`crate::tools::FetchUrlTool`] to turn a fetched web page into ingestible
+//! memory chunks.
+
+use scraper::{ElementRef, Html, Selector};
+
+const CONTENT_SELECTOR: &str = "p, li, h1, h2, h3, h4, h5, h6, blockquote, pre";
+const SKIPPED_ANCESTORS: [&str; 5] = ["script", "style", "nav", "header", "footer"];
+
+/// Walk the DOM collecting text from content-bearing nodes (`p`, `li`,
+/// `h1`-`h6`, `blockquote`, `pre`), discarding anything under a `script`,
+/// `style`, `nav`, `header`, or `footer` subtree, and join the collected
+/// blocks with blank lines.
+pub fn extract_readable_text(html: &str) -> String {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse(CONTENT_SELECTOR).expect("CONTENT_SELECTOR is valid CSS");
+
+    document
+        .select(&selector)
+        .filter(|element| !has_skipped_ancestor(*element))
+        .filter_map(|element| {
+            let collapsed = element.text().collect::<Vec<_>>().join(" ");
+            let collapsed = collapsed.split_whitespace().collect::<Vec<_>>().join(" ");
+            (!collapsed.is_empty()).then_some(collapsed)
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn has_skipped_ancestor(element: ElementRef) -> bool {
+    element
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(|ancestor| SKIPPED_ANCESTORS.contains(&ancestor.value().name()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_readable_text_collects_content_nodes() {
+        let html = "<html><body><h1>Title</h1><p>First paragraph.</p><ul><li>Item one</li></ul></body></html>";
+        let text = extract_readable_text(html);
+        assert!(text.contains("Title"));
+        assert!(text.contains("First paragraph."));
+        assert!(text.contains("Item one"));
+    }
+
+    #[test]
+    fn test_extract_readable_text_skips_nav_script_and_footer() {
+        let html = "<html><body>\
+            <nav><p>Skip this nav link</p></nav>\
+            <script>var x = 'skip this script';</script>\
+            <p>Keep this paragraph.</p>\
+            <footer><p>Skip this footer</p></footer>\
+            </body></html>";
+        let text = extract_readable_text(html);
+        assert!(!text.contains("Skip this nav link"));
+        assert!(!text.contains("skip this script"));
+        assert!(!text.contains("Skip this footer"));
+        assert!(text.contains("Keep this paragraph."));
+    }
+
+    #[test]
+    fn test_extract_readable_text_collapses_whitespace() {
+        let html = "<p>Lots   of\n\n  whitespace   here.</p>";
+        let text = extract_readable_text(html);
+        assert_eq!(text, "Lots of whitespace here.");
+    }
+}
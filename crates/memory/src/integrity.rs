@@ -0,0 +1,166 @@
+//! Content-addressed integrity for stored chunks: a manifest mapping
+//! `chunk_id -> {hash, length, version}`, optionally signed with an ed25519
+//! key so a remote/shared chunk store can be trusted without trusting the
+//! transport (see [`crate::manager::MemoryManager::verify_store`]).
+
+use std::collections::BTreeMap;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+/// SHA-256 digest of a chunk's canonical bytes (its stored text), as lowercase hex.
+pub fn chunk_hash(text: &str) -> String {
+    let digest = Sha256::digest(text.as_bytes());
+    hex::encode(digest)
+}
+
+/// One chunk's recorded integrity state.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ManifestEntry {
+    pub hash: String,
+    pub length: u64,
+    /// Incremented each time the chunk is (re-)written, so a manifest
+    /// consumer can tell a stale-but-otherwise-valid entry from the latest.
+    pub version: u32,
+}
+
+/// `chunk_id -> ManifestEntry` for every chunk this manager has written,
+/// kept in a `BTreeMap` so its serialized bytes are stable and thus signable.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct Manifest {
+    pub entries: BTreeMap<String, ManifestEntry>,
+}
+
+impl Manifest {
+    /// Canonical bytes signed/verified by [`sign_manifest`]/[`verify_manifest`] — stable
+    /// because `entries` is a `BTreeMap`, so equal content always serializes
+    /// identically regardless of insertion order.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("Manifest serializes infallibly")
+    }
+
+    pub fn load(path: &std::path::Path) -> Self {
+        std::fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> anyhow::Result<()> {
+        std::fs::write(path, self.canonical_bytes())?;
+        Ok(())
+    }
+}
+
+/// A [`Manifest`] together with an ed25519 signature over its canonical bytes.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SignedManifest {
+    pub manifest: Manifest,
+    /// Lowercase hex-encoded ed25519 signature over `manifest.canonical_bytes()`.
+    pub signature: String,
+}
+
+/// Sign `manifest`'s canonical bytes with `signing_key`.
+pub fn sign_manifest(manifest: &Manifest, signing_key: &SigningKey) -> SignedManifest {
+    let signature = signing_key.sign(&manifest.canonical_bytes());
+    SignedManifest {
+        manifest: manifest.clone(),
+        signature: hex::encode(signature.to_bytes()),
+    }
+}
+
+/// Verify `signed`'s signature against `public_key`, failing closed: any
+/// malformed signature or mismatch is an error, never a silent pass.
+pub fn verify_manifest(signed: &SignedManifest, public_key: &VerifyingKey) -> anyhow::Result<()> {
+    let sig_bytes = hex::decode(&signed.signature)
+        .map_err(|e| anyhow::anyhow!("manifest signature is not valid hex: {e}"))?;
+    let sig_bytes: [u8; 64] = sig_bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("manifest signature has the wrong length"))?;
+    let signature = Signature::from_bytes(&sig_bytes);
+
+    public_key
+        .verify(&signed.manifest.canonical_bytes(), &signature)
+        .map_err(|e| anyhow::anyhow!("manifest signature verification failed: {e}"))
+}
+
+/// Parse a hex-encoded 32-byte ed25519 seed into a [`SigningKey`].
+pub fn signing_key_from_hex(hex_seed: &str) -> anyhow::Result<SigningKey> {
+    let bytes = hex::decode(hex_seed)
+        .map_err(|e| anyhow::anyhow!("signing key is not valid hex: {e}"))?;
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("signing key must be 32 bytes"))?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+/// Parse a hex-encoded 32-byte ed25519 public key into a [`VerifyingKey`].
+pub fn verifying_key_from_hex(hex_key: &str) -> anyhow::Result<VerifyingKey> {
+    let bytes = hex::decode(hex_key)
+        .map_err(|e| anyhow::anyhow!("public key is not valid hex: {e}"))?;
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("public key must be 32 bytes"))?;
+    VerifyingKey::from_bytes(&bytes).map_err(|e| anyhow::anyhow!("invalid public key: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_hash_is_deterministic_and_content_sensitive() {
+        assert_eq!(chunk_hash("hello"), chunk_hash("hello"));
+        assert_ne!(chunk_hash("hello"), chunk_hash("hellp"));
+    }
+
+    #[test]
+    fn test_manifest_canonical_bytes_are_order_independent() {
+        let mut a = Manifest::default();
+        a.entries.insert("2".into(), ManifestEntry { hash: "h2".into(), length: 2, version: 1 });
+        a.entries.insert("1".into(), ManifestEntry { hash: "h1".into(), length: 1, version: 1 });
+
+        let mut b = Manifest::default();
+        b.entries.insert("1".into(), ManifestEntry { hash: "h1".into(), length: 1, version: 1 });
+        b.entries.insert("2".into(), ManifestEntry { hash: "h2".into(), length: 2, version: 1 });
+
+        assert_eq!(a.canonical_bytes(), b.canonical_bytes());
+    }
+
+    #[test]
+    fn test_sign_and_verify_manifest_round_trip() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = signing_key.verifying_key();
+
+        let mut manifest = Manifest::default();
+        manifest.entries.insert("1".into(), ManifestEntry { hash: "h1".into(), length: 1, version: 1 });
+
+        let signed = sign_manifest(&manifest, &signing_key);
+        assert!(verify_manifest(&signed, &public_key).is_ok());
+    }
+
+    #[test]
+    fn test_verify_manifest_rejects_tampered_content() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = signing_key.verifying_key();
+
+        let mut manifest = Manifest::default();
+        manifest.entries.insert("1".into(), ManifestEntry { hash: "h1".into(), length: 1, version: 1 });
+        let mut signed = sign_manifest(&manifest, &signing_key);
+
+        signed.manifest.entries.get_mut("1").unwrap().hash = "tampered".into();
+        assert!(verify_manifest(&signed, &public_key).is_err());
+    }
+
+    #[test]
+    fn test_verify_manifest_rejects_wrong_key() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let other_key = SigningKey::from_bytes(&[9u8; 32]).verifying_key();
+
+        let mut manifest = Manifest::default();
+        manifest.entries.insert("1".into(), ManifestEntry { hash: "h1".into(), length: 1, version: 1 });
+        let signed = sign_manifest(&manifest, &signing_key);
+
+        assert!(verify_manifest(&signed, &other_key).is_err());
+    }
+}
@@ -0,0 +1,17 @@
+//! Agent long-term memory: hybrid vector + keyword search over daily logs
+//! and long-term memory files (`MEMORY.md`, `memory/*.md`), with pluggable
+//! storage backends.
+
+pub mod config;
+pub mod embeddings;
+pub mod html_extract;
+pub mod integrity;
+pub mod manager;
+pub mod schema;
+pub mod search;
+pub mod store;
+pub mod store_postgres;
+pub mod store_sqlite;
+pub mod tools;
+pub mod types;
+pub mod watch;
@@ -0,0 +1,1052 @@
+//! Owns the [`crate::store::MemoryStore`], embeds and chunks content, and
+//! serves hybrid search + memory-file writes to the agent tools in
+//! [`crate::tools`].
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use moltis_agents::memory_writer::{MemoryWriteResult, MemoryWriter};
+
+use crate::{
+    config::{CitationMode, MemoryConfig},
+    embeddings::EmbeddingProvider,
+    integrity::{self, Manifest, ManifestEntry, SignedManifest},
+    search::{cosine_similarity, keyword_score, SearchResult},
+    store::MemoryStore,
+    types::MemoryChunk,
+};
+
+/// Maximum content size accepted by [`MemoryManager::write_memory`].
+const MAX_CONTENT_BYTES: usize = 50 * 1024;
+
+/// Counts returned by [`MemoryManager::crawl`].
+#[derive(Debug, Clone, Copy, Default, serde::Serialize)]
+pub struct CrawlSummary {
+    pub indexed: usize,
+    pub skipped: usize,
+    pub chunks: usize,
+}
+
+/// Result of [`MemoryManager::delete_chunk`].
+#[derive(Debug, Clone, Default)]
+pub struct DeleteOutcome {
+    pub found: bool,
+    pub path: Option<String>,
+    pub file_deleted: bool,
+}
+
+/// Result of [`MemoryManager::edit_file`].
+#[derive(Debug, Clone, Copy)]
+pub struct EditOutcome {
+    pub bytes_before: usize,
+    pub bytes_after: usize,
+    pub chunks: usize,
+}
+
+/// One passage's provenance within [`AnswerResult::context`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SourceRef {
+    pub path: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub score: f32,
+}
+
+/// Result of [`MemoryManager::answer`].
+#[derive(Debug, Clone)]
+pub struct AnswerResult {
+    pub context: String,
+    pub sources: Vec<SourceRef>,
+}
+
+/// Joins merged passages within [`AnswerResult::context`].
+const PASSAGE_SEPARATOR: &str = "\n\n---\n\n";
+
+/// Whether a [`DirEntry`] describes a directory or a file, as returned by
+/// [`MemoryManager::list_dir`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum PathEntryType {
+    Dir,
+    File,
+}
+
+/// One entry returned by [`MemoryManager::list_dir`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DirEntry {
+    /// Directory names are suffixed with `/` for easy display.
+    pub name: String,
+    pub path_type: PathEntryType,
+    pub size: u64,
+    /// Last-modified time as a Unix timestamp, when the platform reports one.
+    pub mtime: Option<i64>,
+}
+
+/// Counts returned by [`MemoryManager::ingest_dir`].
+#[derive(Debug, Clone, Copy, Default, serde::Serialize)]
+pub struct IngestSummary {
+    pub indexed: usize,
+    pub skipped: usize,
+    pub chunks: usize,
+}
+
+/// Result of [`MemoryManager::verify_store`].
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct VerifyReport {
+    /// Chunks whose hash matched the manifest.
+    pub checked: usize,
+    /// Chunks whose stored text no longer matches its recorded hash.
+    pub tampered: Vec<String>,
+    /// Chunks present in the store with no corresponding manifest entry.
+    pub missing_from_manifest: Vec<String>,
+    /// Manifest entries with no corresponding chunk in the store.
+    pub missing_chunks: Vec<String>,
+    /// Whether the on-disk manifest's signature was checked against
+    /// [`crate::config::MemoryConfig::manifest_public_key`] (and passed) —
+    /// `false` when no public key is configured, in which case hashes are
+    /// still checked but the manifest itself is untrusted.
+    pub signature_verified: bool,
+}
+
+impl VerifyReport {
+    pub fn is_clean(&self) -> bool {
+        self.tampered.is_empty() && self.missing_from_manifest.is_empty() && self.missing_chunks.is_empty()
+    }
+}
+
+/// Coordinates chunking, embedding, storage, and hybrid search over a
+/// [`MemoryConfig`]'s `memory_dirs`.
+pub struct MemoryManager {
+    config: MemoryConfig,
+    store: Box<dyn MemoryStore>,
+    embedder: Box<dyn EmbeddingProvider>,
+    /// `(root, extension)` pairs a previous [`Self::crawl`] call indexed in
+    /// full (every matching file under that walked root was included, none
+    /// dropped by `max_files`), so a later crawl of the same root can skip
+    /// them. Keyed by root as well as extension so a completed crawl of one
+    /// root doesn't cause a later crawl of an unrelated root to skip that
+    /// extension.
+    crawled_extensions: Mutex<HashSet<(String, String)>>,
+    /// Content-addressing manifest (see [`crate::integrity`]), persisted
+    /// alongside `data_dir` so [`Self::verify_store`] can detect tampering
+    /// even across restarts.
+    manifest: Mutex<Manifest>,
+}
+
+impl MemoryManager {
+    pub fn new(
+        config: MemoryConfig,
+        store: Box<dyn MemoryStore>,
+        embedder: Box<dyn EmbeddingProvider>,
+    ) -> Self {
+        let manifest = Manifest::load(&manifest_path(&config));
+        Self {
+            config,
+            store,
+            embedder,
+            crawled_extensions: Mutex::new(HashSet::new()),
+            manifest: Mutex::new(manifest),
+        }
+    }
+
+    pub fn citation_mode(&self) -> CitationMode {
+        self.config.citation_mode
+    }
+
+    /// Paths the filesystem watcher should monitor (see [`crate::watch`]).
+    pub(crate) fn memory_dirs(&self) -> &[PathBuf] {
+        &self.config.memory_dirs
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.config
+            .data_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    /// Record `chunk_id`'s hash/length in the manifest (bumping its version
+    /// if it already had one) and persist the manifest, re-signing it first
+    /// when [`crate::config::MemoryConfig::manifest_signing_key`] is set.
+    fn record_chunk(&self, chunk_id: &str, text: &str) -> anyhow::Result<()> {
+        let mut manifest = self.manifest.lock().unwrap();
+        let version = manifest.entries.get(chunk_id).map(|e| e.version + 1).unwrap_or(1);
+        manifest.entries.insert(
+            chunk_id.to_string(),
+            ManifestEntry { hash: integrity::chunk_hash(text), length: text.len() as u64, version },
+        );
+        self.persist_manifest(&manifest)
+    }
+
+    /// Remove `chunk_id` from the manifest and persist the change.
+    fn forget_chunk(&self, chunk_id: &str) -> anyhow::Result<()> {
+        let mut manifest = self.manifest.lock().unwrap();
+        manifest.entries.remove(chunk_id);
+        self.persist_manifest(&manifest)
+    }
+
+    fn persist_manifest(&self, manifest: &Manifest) -> anyhow::Result<()> {
+        manifest.save(&manifest_path(&self.config))?;
+        if let Some(signing_key) = &self.config.manifest_signing_key {
+            let signing_key = integrity::signing_key_from_hex(signing_key)?;
+            let signed = integrity::sign_manifest(manifest, &signing_key);
+            let bytes = serde_json::to_vec(&signed)?;
+            std::fs::write(signature_path(&self.config), bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Load the signed manifest from disk, verifying its signature against
+    /// [`crate::config::MemoryConfig::manifest_public_key`] when configured.
+    /// Returns the manifest and whether a signature was checked and passed.
+    fn load_trusted_manifest(&self) -> anyhow::Result<(Manifest, bool)> {
+        let Some(public_key) = &self.config.manifest_public_key else {
+            return Ok((self.manifest.lock().unwrap().clone(), false));
+        };
+
+        let bytes = std::fs::read(signature_path(&self.config))
+            .map_err(|e| anyhow::anyhow!("no signed manifest found: {e}"))?;
+        let signed: SignedManifest = serde_json::from_slice(&bytes)
+            .map_err(|e| anyhow::anyhow!("signed manifest is malformed: {e}"))?;
+        let public_key = integrity::verifying_key_from_hex(public_key)?;
+        integrity::verify_manifest(&signed, &public_key)?;
+        Ok((signed.manifest, true))
+    }
+
+    /// Check every stored chunk's hash against the manifest. When
+    /// [`crate::config::MemoryConfig::manifest_public_key`] is set, the signed
+    /// manifest's signature is verified first and a missing or invalid one is
+    /// an error; without a key, hashes are checked against the unsigned
+    /// in-memory manifest and `signature_verified` is `false`. Reports
+    /// chunks whose content no longer matches its recorded hash, chunks with
+    /// no manifest entry, and manifest entries with no corresponding chunk.
+    pub async fn verify_store(&self) -> anyhow::Result<VerifyReport> {
+        let (manifest, signature_verified) = self.load_trusted_manifest()?;
+
+        let mut report = VerifyReport { signature_verified, ..Default::default() };
+        let mut seen = HashSet::new();
+
+        for chunk in self.store.all_chunks().await? {
+            seen.insert(chunk.id.clone());
+            match manifest.entries.get(&chunk.id) {
+                Some(entry) if entry.hash == integrity::chunk_hash(&chunk.text) => report.checked += 1,
+                Some(_) => report.tampered.push(chunk.id),
+                None => report.missing_from_manifest.push(chunk.id),
+            }
+        }
+
+        for chunk_id in manifest.entries.keys() {
+            if !seen.contains(chunk_id) {
+                report.missing_chunks.push(chunk_id.clone());
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Re-walk every path in `memory_dirs`, re-chunking and re-embedding any
+    /// file found, and clear chunks for files that have disappeared isn't
+    /// attempted here — see [`crate::tools::MemoryCrawlTool`] and the
+    /// filesystem watcher for incremental, gitignore-aware alternatives.
+    pub async fn sync(&self) -> anyhow::Result<()> {
+        for root in &self.config.memory_dirs {
+            if root.is_file() {
+                self.index_file(root).await?;
+            } else if root.is_dir() {
+                for entry in walk_files(root) {
+                    self.index_file(&entry).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Re-chunk and re-embed a single file, replacing any chunks previously
+    /// indexed under its path. Public so the watcher and `memory_reindex`
+    /// tool can re-sync a single path without a full `sync()`.
+    pub async fn index_file(&self, abs_path: &Path) -> anyhow::Result<usize> {
+        let text = match std::fs::read_to_string(abs_path) {
+            Ok(t) => t,
+            Err(_) => return Ok(0), // not UTF-8 text, or vanished mid-walk; skip
+        };
+        let rel_path = self.relative_path(abs_path);
+        self.index_text(&rel_path, &text).await
+    }
+
+    /// Replace the chunks stored under `rel_path` with ones freshly chunked
+    /// and embedded from `text`. Returns the number of chunks produced.
+    async fn index_text(&self, rel_path: &str, text: &str) -> anyhow::Result<usize> {
+        Ok(self.ingest_text(rel_path, "memory_file", text).await?.len())
+    }
+
+    /// Chunk, embed, and store `text` under an arbitrary `path` key tagged
+    /// with `source`, replacing any chunks previously stored under that
+    /// path. Unlike [`Self::index_file`], `path` need not resolve to a file
+    /// under `data_dir` — used for content that doesn't live on local disk,
+    /// e.g. a fetched web page ([`crate::tools::FetchUrlTool`]) or a crate's
+    /// source file ([`crate::tools::FetchCrateTool`]). Returns the created
+    /// chunk ids.
+    pub async fn ingest_text(&self, path: &str, source: &str, text: &str) -> anyhow::Result<Vec<String>> {
+        // Drop the manifest entries for whatever chunks `path` held before,
+        // since `delete_by_path` is about to make their ids unreachable.
+        for stale in self.store.all_chunks().await?.into_iter().filter(|c| c.path == path) {
+            self.forget_chunk(&stale.id)?;
+        }
+        self.store.delete_by_path(path).await?;
+
+        let chunks = chunk_lines(text, self.config.chunk_size, self.config.chunk_overlap);
+        let mut ids = Vec::with_capacity(chunks.len());
+        for (start_line, end_line, chunk_text) in &chunks {
+            let embedding = self.embedder.embed(chunk_text).await?;
+            let id = uuid::Uuid::new_v4().to_string();
+            self.store
+                .insert_chunk(&MemoryChunk {
+                    id: id.clone(),
+                    path: path.to_string(),
+                    source: source.to_string(),
+                    start_line: *start_line,
+                    end_line: *end_line,
+                    text: chunk_text.clone(),
+                    embedding,
+                })
+                .await?;
+            self.record_chunk(&id, chunk_text)?;
+            ids.push(id);
+        }
+        Ok(ids)
+    }
+
+    /// Remove every indexed chunk for `abs_path`, e.g. after it's deleted or
+    /// renamed away from.
+    pub async fn remove_file(&self, abs_path: &Path) -> anyhow::Result<()> {
+        self.store.delete_by_path(&self.relative_path(abs_path)).await
+    }
+
+    /// Force a targeted re-sync of a single path, relative to `data_dir`, on
+    /// demand (the `memory_reindex` tool's entry point). Returns the number
+    /// of chunks produced, or clears stale chunks and returns 0 if the path
+    /// no longer exists.
+    pub async fn reindex(&self, rel_path: &str) -> anyhow::Result<usize> {
+        if rel_path.starts_with('/') || rel_path.contains("..") {
+            anyhow::bail!("invalid path: {rel_path}");
+        }
+        let abs_path = self.data_dir().join(rel_path);
+        if abs_path.is_file() {
+            self.index_file(&abs_path).await
+        } else {
+            self.store.delete_by_path(rel_path).await?;
+            Ok(0)
+        }
+    }
+
+    /// Remove a single chunk by id, from the store only, or — when
+    /// `delete_file` is set — also delete the chunk's source file from disk
+    /// and clear every other chunk indexed from it. Returns `found: false`
+    /// if no chunk with `chunk_id` exists. `delete_file` only makes sense for
+    /// chunks backed by a real file under `data_dir` (`source` of
+    /// `memory_file` or `file_ingest`, from [`Self::write_memory`]/
+    /// [`Self::crawl`] or [`Self::ingest_dir`]); chunks from
+    /// [`crate::tools::FetchUrlTool`] or [`crate::tools::FetchCrateTool`]
+    /// have no such file, so `delete_file: true` on one of those is
+    /// rejected upfront rather than erroring confusingly on path validation.
+    pub async fn delete_chunk(&self, chunk_id: &str, delete_file: bool) -> anyhow::Result<DeleteOutcome> {
+        let Some(chunk) = self.store.get_chunk(chunk_id).await? else {
+            return Ok(DeleteOutcome::default());
+        };
+
+        if delete_file {
+            if !matches!(chunk.source.as_str(), "memory_file" | "file_ingest") {
+                anyhow::bail!(
+                    "chunk {chunk_id} has no on-disk file to delete (source: {:?}); \
+                     delete it with delete_file: false instead",
+                    chunk.source
+                );
+            }
+            let abs_path = validate_data_dir_path(&self.data_dir(), &chunk.path)?;
+            if abs_path.exists() {
+                std::fs::remove_file(&abs_path)?;
+            }
+            for sibling in self.store.all_chunks().await?.into_iter().filter(|c| c.path == chunk.path) {
+                self.forget_chunk(&sibling.id)?;
+            }
+            self.store.delete_by_path(&chunk.path).await?;
+        } else {
+            self.forget_chunk(chunk_id)?;
+            self.store.delete_chunk(chunk_id).await?;
+        }
+
+        Ok(DeleteOutcome {
+            found: true,
+            path: Some(chunk.path),
+            file_deleted: delete_file,
+        })
+    }
+
+    /// Apply a structured update to `file`: first remove every occurrence of
+    /// each `deletes` pattern, then apply each `(match, replacement)` in
+    /// `replaces`, then append each entry in `adds` — in that order, so a
+    /// single call can surgically revise a memory file instead of requiring
+    /// a full rewrite. Re-indexes the file afterward.
+    pub async fn edit_file(
+        &self,
+        file: &str,
+        deletes: &[String],
+        replaces: &[(String, String)],
+        adds: &[String],
+    ) -> anyhow::Result<EditOutcome> {
+        let abs_path = validate_memory_path(&self.data_dir(), file)?;
+        let mut content = std::fs::read_to_string(&abs_path).unwrap_or_default();
+        let bytes_before = content.len();
+
+        for pattern in deletes {
+            content = content.replace(pattern.as_str(), "");
+        }
+        for (from, to) in replaces {
+            content = content.replace(from.as_str(), to.as_str());
+        }
+        for addition in adds {
+            content = if content.trim().is_empty() {
+                addition.clone()
+            } else {
+                format!("{content}\n\n{addition}")
+            };
+        }
+
+        if let Some(parent) = abs_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&abs_path, &content)?;
+        let bytes_after = content.len();
+
+        let chunks = self.index_file(&abs_path).await?;
+        Ok(EditOutcome { bytes_before, bytes_after, chunks })
+    }
+
+    /// Direct store access for tests that need to bypass manifest
+    /// bookkeeping to simulate out-of-band tampering.
+    #[cfg(test)]
+    pub(crate) fn store(&self) -> &dyn MemoryStore {
+        self.store.as_ref()
+    }
+
+    /// Path relative to `data_dir`, with forward slashes, for storage as the
+    /// chunk's `path` field.
+    pub(crate) fn relative_path(&self, abs_path: &Path) -> String {
+        abs_path
+            .strip_prefix(self.data_dir())
+            .unwrap_or(abs_path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
+    /// Hybrid vector + keyword search, combined via `vector_weight`/`keyword_weight`.
+    /// Backends that can do this more efficiently in-database (see
+    /// [`crate::store::MemoryStore::hybrid_search`]) are preferred; otherwise
+    /// this falls back to scoring every chunk in-process.
+    pub async fn search(&self, query: &str, limit: usize) -> anyhow::Result<Vec<SearchResult>> {
+        let query_embedding = self.embedder.embed(query).await?;
+
+        if let Some(results) = self
+            .store
+            .hybrid_search(
+                &query_embedding,
+                query,
+                self.config.vector_weight,
+                self.config.keyword_weight,
+                limit,
+            )
+            .await?
+        {
+            return Ok(results);
+        }
+
+        let chunks = self.store.all_chunks().await?;
+
+        let mut scored: Vec<SearchResult> = chunks
+            .into_iter()
+            .map(|chunk| {
+                let vector_score = cosine_similarity(&query_embedding, &chunk.embedding);
+                let kw_score = keyword_score(query, &chunk.text, &self.config.typo_tolerance);
+                let score =
+                    self.config.vector_weight * vector_score + self.config.keyword_weight * kw_score;
+                SearchResult {
+                    chunk_id: chunk.id,
+                    path: chunk.path,
+                    source: chunk.source,
+                    start_line: chunk.start_line,
+                    end_line: chunk.end_line,
+                    score,
+                    text: chunk.text,
+                }
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(limit);
+        Ok(scored)
+    }
+
+    /// Fetch a chunk and verify its text against the hash recorded for it
+    /// at write time, refusing to return content that no longer matches —
+    /// the read-time half of [`crate::integrity`]'s tamper detection.
+    pub async fn get_chunk(&self, chunk_id: &str) -> anyhow::Result<Option<MemoryChunk>> {
+        let Some(chunk) = self.store.get_chunk(chunk_id).await? else {
+            return Ok(None);
+        };
+
+        if let Some(entry) = self.manifest.lock().unwrap().entries.get(chunk_id) {
+            let actual = integrity::chunk_hash(&chunk.text);
+            if actual != entry.hash {
+                anyhow::bail!(
+                    "integrity check failed for chunk {chunk_id}: stored content hash {actual} does not match recorded hash {}",
+                    entry.hash
+                );
+            }
+        }
+
+        Ok(Some(chunk))
+    }
+
+    /// Recursively index files under `root` (relative to `data_dir`),
+    /// respecting `.gitignore`/`.ignore` rules. When `all_files` is false,
+    /// only files whose extension is in [`MemoryConfig::crawl_extensions`]
+    /// are indexed, and an extension is skipped entirely once a prior
+    /// `crawl` call of the same `root` already indexed every matching file
+    /// for it. Stops after
+    /// `max_files` files have been indexed in this call.
+    pub async fn crawl(&self, root: &str, all_files: bool, max_files: usize) -> anyhow::Result<CrawlSummary> {
+        let abs_root = validate_crawl_root(&self.data_dir(), root)?;
+
+        let mut by_ext: std::collections::HashMap<String, Vec<PathBuf>> = std::collections::HashMap::new();
+        let mut untyped: Vec<PathBuf> = Vec::new();
+
+        for entry in ignore::WalkBuilder::new(&abs_root).build().flatten() {
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            let path = entry.into_path();
+            match path.extension().and_then(|e| e.to_str()) {
+                Some(ext) if self.config.crawl_extensions.iter().any(|allowed| allowed == ext) => {
+                    by_ext.entry(ext.to_string()).or_default().push(path);
+                }
+                _ if all_files => untyped.push(path),
+                _ => {}
+            }
+        }
+
+        let already_done = self.crawled_extensions.lock().unwrap().clone();
+
+        let mut summary = CrawlSummary::default();
+        let mut newly_complete = Vec::new();
+
+        for (ext, paths) in by_ext {
+            if already_done.contains(&(root.to_string(), ext.clone())) {
+                summary.skipped += paths.len();
+                continue;
+            }
+            let total = paths.len();
+            let mut included = 0;
+            for path in paths {
+                if summary.indexed >= max_files {
+                    break;
+                }
+                summary.chunks += self.index_file(&path).await?;
+                summary.indexed += 1;
+                included += 1;
+            }
+            summary.skipped += total - included;
+            if included == total {
+                newly_complete.push((root.to_string(), ext));
+            }
+        }
+
+        if all_files {
+            let total = untyped.len();
+            let mut included = 0;
+            for path in untyped {
+                if summary.indexed >= max_files {
+                    break;
+                }
+                summary.chunks += self.index_file(&path).await?;
+                summary.indexed += 1;
+                included += 1;
+            }
+            summary.skipped += total - included;
+        }
+
+        let mut done = self.crawled_extensions.lock().unwrap();
+        done.extend(newly_complete);
+
+        Ok(summary)
+    }
+
+    /// List the immediate entries of `path` (relative to `data_dir`), sorted
+    /// by name, for an agent to browse a local corpus before deciding what
+    /// to [`Self::ingest_dir`].
+    pub fn list_dir(&self, path: &str) -> anyhow::Result<Vec<DirEntry>> {
+        let abs = validate_crawl_root(&self.data_dir(), path)?;
+
+        let mut entries = Vec::new();
+        for entry in std::fs::read_dir(&abs)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            let is_dir = metadata.is_dir();
+
+            let mut name = entry.file_name().to_string_lossy().into_owned();
+            if is_dir {
+                name.push('/');
+            }
+
+            let mtime = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs() as i64);
+
+            entries.push(DirEntry {
+                name,
+                path_type: if is_dir { PathEntryType::Dir } else { PathEntryType::File },
+                size: metadata.len(),
+                mtime,
+            });
+        }
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+
+    /// Recursively walk `root` (relative to `data_dir`) up to `max_depth`
+    /// levels deep, reading each file whose extension is in `extensions`
+    /// (every file, when `extensions` is empty) and saving its contents as
+    /// chunks keyed by its path relative to `data_dir`, so it's retrievable
+    /// with [`Self::get_chunk`]/`memory_get`. Unlike [`Self::crawl`], this
+    /// does not respect `.gitignore` — it's meant for explicitly pointing at
+    /// a known local corpus.
+    pub async fn ingest_dir(
+        &self,
+        root: &str,
+        extensions: &[String],
+        max_depth: usize,
+    ) -> anyhow::Result<IngestSummary> {
+        let abs_root = validate_crawl_root(&self.data_dir(), root)?;
+
+        let mut summary = IngestSummary::default();
+        let mut stack: Vec<(PathBuf, usize)> = vec![(abs_root, 0)];
+
+        while let Some((dir, depth)) = stack.pop() {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    if depth < max_depth {
+                        stack.push((path, depth + 1));
+                    }
+                    continue;
+                }
+
+                let matches = extensions.is_empty()
+                    || path
+                        .extension()
+                        .and_then(|e| e.to_str())
+                        .is_some_and(|ext| extensions.iter().any(|allowed| allowed == ext));
+                if !matches {
+                    summary.skipped += 1;
+                    continue;
+                }
+
+                let Ok(text) = std::fs::read_to_string(&path) else {
+                    summary.skipped += 1;
+                    continue;
+                };
+
+                let rel_path = self.relative_path(&path);
+                let ids = self.ingest_text(&rel_path, "file_ingest", &text).await?;
+                summary.chunks += ids.len();
+                summary.indexed += 1;
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Run [`Self::search`], merge adjacent/overlapping passages from the
+    /// same source file, and assemble the highest-scoring ones (dropping the
+    /// rest) into a single ready-to-prompt context string under
+    /// `max_context_chars` (the top passage is always included, even when it
+    /// alone exceeds the budget), with citations gated by [`Self::citation_mode`]
+    /// the same way [`Self::search`]'s callers already do.
+    pub async fn answer(
+        &self,
+        query: &str,
+        limit: usize,
+        max_context_chars: usize,
+    ) -> anyhow::Result<AnswerResult> {
+        let results = self.search(query, limit).await?;
+        let mut passages = merge_adjacent_passages(results);
+        passages.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+
+        let include_citations = SearchResult::should_include_citations(&passages, self.citation_mode());
+
+        let mut included: Vec<(SourceRef, String)> = Vec::new();
+        let mut used_chars = 0usize;
+        for passage in passages {
+            let piece = if include_citations {
+                passage.text_with_citation()
+            } else {
+                passage.text.clone()
+            };
+            let additional = piece.len() + if included.is_empty() { 0 } else { PASSAGE_SEPARATOR.len() };
+            if !included.is_empty() && used_chars + additional > max_context_chars {
+                break;
+            }
+            used_chars += additional;
+            included.push((
+                SourceRef {
+                    path: passage.path,
+                    start_line: passage.start_line,
+                    end_line: passage.end_line,
+                    score: passage.score,
+                },
+                piece,
+            ));
+        }
+
+        let context = included
+            .iter()
+            .map(|(_, piece)| piece.as_str())
+            .collect::<Vec<_>>()
+            .join(PASSAGE_SEPARATOR);
+        let sources = included.into_iter().map(|(source, _)| source).collect();
+
+        Ok(AnswerResult { context, sources })
+    }
+}
+
+/// Collapse neighboring or overlapping results from the same `path` into
+/// single passages, so a caller gets coherent text instead of fragments.
+/// Within each path, results are merged in `start_line` order when the next
+/// result begins at or before the current passage's `end_line + 1`.
+fn merge_adjacent_passages(results: Vec<SearchResult>) -> Vec<SearchResult> {
+    let mut by_path: std::collections::HashMap<String, Vec<SearchResult>> = std::collections::HashMap::new();
+    let mut path_order: Vec<String> = Vec::new();
+    for result in results {
+        if !by_path.contains_key(&result.path) {
+            path_order.push(result.path.clone());
+        }
+        by_path.entry(result.path.clone()).or_default().push(result);
+    }
+
+    let mut merged = Vec::new();
+    for path in path_order {
+        let mut group = by_path.remove(&path).unwrap_or_default();
+        group.sort_by_key(|r| r.start_line);
+
+        let mut current: Option<SearchResult> = None;
+        for result in group {
+            current = Some(match current {
+                None => result,
+                Some(mut acc) if result.start_line <= acc.end_line + 1 => {
+                    acc.end_line = acc.end_line.max(result.end_line);
+                    acc.text = format!("{}\n{}", acc.text, result.text);
+                    acc.score = acc.score.max(result.score);
+                    acc.chunk_id = format!("{},{}", acc.chunk_id, result.chunk_id);
+                    acc
+                }
+                Some(acc) => {
+                    merged.push(acc);
+                    result
+                }
+            });
+        }
+        if let Some(acc) = current {
+            merged.push(acc);
+        }
+    }
+    merged
+}
+
+#[async_trait::async_trait]
+impl MemoryWriter for MemoryManager {
+    async fn write_memory(
+        &self,
+        file: &str,
+        content: &str,
+        append: bool,
+    ) -> anyhow::Result<MemoryWriteResult> {
+        if content.len() > MAX_CONTENT_BYTES {
+            anyhow::bail!("content exceeds {MAX_CONTENT_BYTES}-byte limit");
+        }
+        let abs_path = validate_memory_path(&self.data_dir(), file)?;
+
+        if let Some(parent) = abs_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let bytes_written = if append && abs_path.exists() {
+            let existing = std::fs::read_to_string(&abs_path).unwrap_or_default();
+            let combined = if existing.trim().is_empty() {
+                content.to_string()
+            } else {
+                format!("{existing}\n\n{content}")
+            };
+            std::fs::write(&abs_path, &combined)?;
+            combined.len()
+        } else {
+            std::fs::write(&abs_path, content)?;
+            content.len()
+        };
+
+        self.index_file(&abs_path).await?;
+
+        Ok(MemoryWriteResult {
+            location: abs_path.to_string_lossy().into_owned(),
+            bytes_written,
+        })
+    }
+}
+
+/// Validate that `file` is `MEMORY.md`, `memory.md`, or a flat `memory/<name>.md`
+/// with a non-empty, space-free stem and no nested subdirectories or path
+/// traversal, then resolve it against `data_dir`.
+pub(crate) fn validate_memory_path(data_dir: &Path, file: &str) -> anyhow::Result<PathBuf> {
+    if file.starts_with('/') || file.contains("..") {
+        anyhow::bail!("invalid memory file path: {file}");
+    }
+
+    let valid_top_level = file == "MEMORY.md" || file == "memory.md";
+    let valid_in_memory_dir = file
+        .strip_prefix("memory/")
+        .map(|rest| {
+            rest.len() > ".md".len()
+                && !rest.contains('/')
+                && rest.ends_with(".md")
+                && !rest.contains(' ')
+        })
+        .unwrap_or(false);
+
+    if !valid_top_level && !valid_in_memory_dir {
+        anyhow::bail!("invalid memory file path: {file}");
+    }
+
+    Ok(data_dir.join(file))
+}
+
+/// Where the integrity manifest (see [`crate::integrity`]) lives for a given
+/// config's `data_dir` — a dotfile, since it's bookkeeping, not content.
+fn manifest_path(config: &MemoryConfig) -> PathBuf {
+    config
+        .data_dir
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".memory_manifest.json")
+}
+
+/// Where the signed manifest (see [`integrity::SignedManifest`]) lives,
+/// written alongside [`manifest_path`] only when a signing key is configured.
+fn signature_path(config: &MemoryConfig) -> PathBuf {
+    config
+        .data_dir
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".memory_manifest.sig.json")
+}
+
+/// Validate that `root` is a relative, non-traversing path that resolves to
+/// an existing directory inside `data_dir` — the same absolute-path/`..`
+/// rejection [`validate_memory_path`] applies to memory files.
+fn validate_crawl_root(data_dir: &Path, root: &str) -> anyhow::Result<PathBuf> {
+    if root.starts_with('/') || root.contains("..") {
+        anyhow::bail!("invalid crawl root: {root}");
+    }
+    let abs = data_dir.join(root);
+    if !abs.starts_with(data_dir) {
+        anyhow::bail!("invalid crawl root: {root}");
+    }
+    if !abs.is_dir() {
+        anyhow::bail!("crawl root is not a directory: {root}");
+    }
+    Ok(abs)
+}
+
+/// Resolve `rel_path` against `data_dir`, applying the same absolute-path/
+/// `..`/containment rejection as [`validate_crawl_root`] but without
+/// requiring the result to exist or be a directory. Unlike
+/// [`validate_memory_path`], this accepts any relative path under
+/// `data_dir` rather than only the `MEMORY.md`-style naming convention —
+/// used for chunks whose path is a real crawled/ingested file rather than
+/// an authored memory note.
+fn validate_data_dir_path(data_dir: &Path, rel_path: &str) -> anyhow::Result<PathBuf> {
+    if rel_path.starts_with('/') || rel_path.contains("..") {
+        anyhow::bail!("invalid path: {rel_path}");
+    }
+    let abs = data_dir.join(rel_path);
+    if !abs.starts_with(data_dir) {
+        anyhow::bail!("invalid path: {rel_path}");
+    }
+    Ok(abs)
+}
+
+/// Recursively collect file paths under `root`, with no gitignore awareness;
+/// see [`crate::tools::MemoryCrawlTool`] for that.
+fn walk_files(root: &Path) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else {
+                out.push(path);
+            }
+        }
+    }
+    out
+}
+
+/// Split `text` into overlapping line-range chunks of roughly `chunk_size`
+/// characters each, overlapping the previous chunk by roughly
+/// `chunk_overlap` characters of trailing context.
+pub(crate) fn chunk_lines(text: &str, chunk_size: usize, chunk_overlap: usize) -> Vec<(u32, u32, String)> {
+    let lines: Vec<&str> = text.lines().collect();
+    if lines.is_empty() {
+        return Vec::new();
+    }
+
+    let mut chunks = Vec::new();
+    let mut start = 0usize;
+    while start < lines.len() {
+        let mut end = start;
+        let mut len = 0usize;
+        while end < lines.len() && (len == 0 || len < chunk_size) {
+            len += lines[end].len() + 1;
+            end += 1;
+        }
+        chunks.push((start as u32 + 1, end as u32, lines[start..end].join("\n")));
+
+        if end >= lines.len() {
+            break;
+        }
+        let mut back = end;
+        let mut overlap_len = 0usize;
+        while back > start && overlap_len < chunk_overlap {
+            back -= 1;
+            overlap_len += lines[back].len() + 1;
+        }
+        start = back.max(start + 1);
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_lines_single_chunk_for_short_text() {
+        let chunks = chunk_lines("line one\nline two", 500, 50);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0], (1, 2, "line one\nline two".to_string()));
+    }
+
+    #[test]
+    fn test_chunk_lines_splits_long_text() {
+        let text = (0..20).map(|i| format!("line {i}")).collect::<Vec<_>>().join("\n");
+        let chunks = chunk_lines(&text, 30, 10);
+        assert!(chunks.len() > 1, "long text should split into multiple chunks");
+    }
+
+    #[test]
+    fn test_validate_memory_path_accepts_top_level() {
+        let dir = PathBuf::from("/data");
+        assert!(validate_memory_path(&dir, "MEMORY.md").is_ok());
+        assert!(validate_memory_path(&dir, "memory/notes.md").is_ok());
+    }
+
+    #[test]
+    fn test_validate_crawl_root_rejects_traversal() {
+        let dir = PathBuf::from("/data");
+        assert!(validate_crawl_root(&dir, "../etc").is_err());
+        assert!(validate_crawl_root(&dir, "/etc").is_err());
+    }
+
+    #[test]
+    fn test_validate_data_dir_path_accepts_arbitrary_relative_paths() {
+        let dir = PathBuf::from("/data");
+        // Unlike `validate_memory_path`, any relative path under `data_dir`
+        // is accepted, not just the MEMORY.md-style naming convention.
+        assert!(validate_data_dir_path(&dir, "src/lib.rs").is_ok());
+        assert!(validate_data_dir_path(&dir, "docs/sub/dir/notes.txt").is_ok());
+    }
+
+    #[test]
+    fn test_validate_data_dir_path_rejects_traversal() {
+        let dir = PathBuf::from("/data");
+        assert!(validate_data_dir_path(&dir, "../etc/passwd").is_err());
+        assert!(validate_data_dir_path(&dir, "/etc/passwd").is_err());
+    }
+
+    fn make_result(chunk_id: &str, path: &str, start_line: u32, end_line: u32, score: f32, text: &str) -> SearchResult {
+        SearchResult {
+            chunk_id: chunk_id.into(),
+            path: path.into(),
+            source: "memory_file".into(),
+            start_line,
+            end_line,
+            score,
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn test_merge_adjacent_passages_combines_overlapping_ranges() {
+        let results = vec![
+            make_result("1", "a.md", 1, 5, 0.9, "first chunk"),
+            make_result("2", "a.md", 4, 10, 0.6, "second chunk"),
+        ];
+        let merged = merge_adjacent_passages(results);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].start_line, 1);
+        assert_eq!(merged[0].end_line, 10);
+        assert_eq!(merged[0].score, 0.9);
+        assert!(merged[0].text.contains("first chunk"));
+        assert!(merged[0].text.contains("second chunk"));
+    }
+
+    #[test]
+    fn test_merge_adjacent_passages_keeps_distant_ranges_separate() {
+        let results = vec![
+            make_result("1", "a.md", 1, 5, 0.9, "first chunk"),
+            make_result("2", "a.md", 50, 55, 0.6, "far away chunk"),
+        ];
+        let merged = merge_adjacent_passages(results);
+        assert_eq!(merged.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_adjacent_passages_keeps_different_paths_separate() {
+        let results = vec![
+            make_result("1", "a.md", 1, 5, 0.9, "a chunk"),
+            make_result("2", "b.md", 1, 5, 0.6, "b chunk"),
+        ];
+        let merged = merge_adjacent_passages(results);
+        assert_eq!(merged.len(), 2);
+    }
+
+    #[test]
+    fn test_validate_memory_path_rejects_invalid() {
+        let dir = PathBuf::from("/data");
+        assert!(validate_memory_path(&dir, "../etc/passwd").is_err());
+        assert!(validate_memory_path(&dir, "/etc/passwd").is_err());
+        assert!(validate_memory_path(&dir, "memory/sub/nested.md").is_err());
+        assert!(validate_memory_path(&dir, "memory/.md").is_err());
+        assert!(validate_memory_path(&dir, "memory/a b c.md").is_err());
+        assert!(validate_memory_path(&dir, "random.md").is_err());
+    }
+}
@@ -0,0 +1,27 @@
+//! SQLite schema migrations for the memory store.
+
+use anyhow::Result;
+use sqlx::SqlitePool;
+
+/// Create the `memory_chunks` table and its indexes if they don't already exist.
+pub async fn run_migrations(pool: &SqlitePool) -> Result<()> {
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS memory_chunks (
+            id TEXT PRIMARY KEY,
+            path TEXT NOT NULL,
+            source TEXT NOT NULL,
+            start_line INTEGER NOT NULL,
+            end_line INTEGER NOT NULL,
+            text TEXT NOT NULL,
+            embedding BLOB NOT NULL
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_memory_chunks_path ON memory_chunks(path)")
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
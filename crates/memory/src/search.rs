@@ -0,0 +1,210 @@
+//! Hybrid vector + keyword scoring over indexed [`crate::types::MemoryChunk`]s.
+
+use std::collections::HashSet;
+
+use crate::config::{CitationMode, TypoTolerance};
+
+/// A single scored search hit, combining the vector and keyword halves.
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub chunk_id: String,
+    pub path: String,
+    pub source: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub score: f32,
+    pub text: String,
+}
+
+impl SearchResult {
+    /// Text with an inline `path#start_line` citation appended.
+    pub fn text_with_citation(&self) -> String {
+        format!("{}\n\n[{}#{}]", self.text, self.path, self.start_line)
+    }
+
+    /// Whether citations should be attached for this result set under `mode`.
+    pub fn should_include_citations(results: &[SearchResult], mode: CitationMode) -> bool {
+        match mode {
+            CitationMode::Always => true,
+            CitationMode::Never => false,
+            CitationMode::WhenMultipleSources => {
+                results.iter().map(|r| r.path.as_str()).collect::<HashSet<_>>().len() > 1
+            }
+        }
+    }
+}
+
+/// Cosine similarity between two equal-length vectors, 0.0 if either is empty
+/// or their norms are zero.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.is_empty() || a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Maximum Levenshtein distance a stored term of length `term_len` may be
+/// from a query term and still count as a match, per `tolerance`.
+fn max_edit_distance(term_len: usize, tolerance: &TypoTolerance) -> usize {
+    if term_len <= tolerance.exact_below_len {
+        0
+    } else if term_len <= tolerance.one_edit_below_len {
+        1.min(tolerance.max_edit_distance)
+    } else {
+        tolerance.max_edit_distance
+    }
+}
+
+/// Levenshtein (edit) distance between two strings.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j + 1] + 1).min(curr[j] + 1).min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// Typo-tolerant keyword score: each query term contributes its best match
+/// among `text`'s words, weighted down by how many edits that match took
+/// (an exact hit outscores a 1-edit hit). Candidates are prefiltered by
+/// length and first character before computing full edit distance, and
+/// capped at `tolerance.max_candidates_per_term` per query term, to keep
+/// scoring cheap on long chunks.
+pub fn keyword_score(query: &str, text: &str, tolerance: &TypoTolerance) -> f32 {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if terms.is_empty() {
+        return 0.0;
+    }
+
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    let total: f32 = terms
+        .iter()
+        .map(|term| {
+            let max_dist = max_edit_distance(term.len(), tolerance);
+            let term_first = term.chars().next();
+
+            let best = words
+                .iter()
+                .filter(|w| w.chars().next() == term_first)
+                .filter(|w| w.len().abs_diff(term.len()) <= max_dist)
+                .take(tolerance.max_candidates_per_term)
+                .filter_map(|w| {
+                    let dist = levenshtein(term, w);
+                    (dist <= max_dist).then_some(dist)
+                })
+                .min();
+
+            match best {
+                Some(dist) => 1.0 - dist as f32 / (max_dist as f32 + 1.0),
+                None => 0.0,
+            }
+        })
+        .sum();
+
+    total / terms.len() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cosine_similarity_identical() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[1.0, 0.0]) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_cosine_similarity_orthogonal() {
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_keyword_score_exact_match() {
+        let tol = TypoTolerance::default();
+        assert_eq!(
+            keyword_score("rust memory", "Rust has great memory safety", &tol),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_keyword_score_partial_match() {
+        let tol = TypoTolerance::default();
+        assert_eq!(
+            keyword_score("rust cooking", "Rust is a language", &tol),
+            0.5
+        );
+    }
+
+    #[test]
+    fn test_keyword_score_tolerates_single_typo() {
+        let tol = TypoTolerance::default();
+        // "databse" (1 edit from "database") should still score above zero,
+        // but below an exact match.
+        let typo_score = keyword_score("databse", "A note about a database.", &tol);
+        assert!(typo_score > 0.0, "1-edit typo should still match");
+        assert!(typo_score < 1.0, "typo match should score lower than exact");
+    }
+
+    #[test]
+    fn test_keyword_score_rejects_short_term_typo() {
+        let tol = TypoTolerance::default();
+        // Short terms (<= exact_below_len) require an exact match.
+        assert_eq!(keyword_score("cat", "The bat sat on a mat.", &tol), 0.0);
+    }
+
+    #[test]
+    fn test_levenshtein_distance() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("rust", "rust"), 0);
+    }
+
+    #[test]
+    fn test_should_include_citations_multiple_sources() {
+        let results = vec![
+            SearchResult {
+                chunk_id: "1".into(),
+                path: "a.md".into(),
+                source: "memory_file".into(),
+                start_line: 1,
+                end_line: 2,
+                score: 1.0,
+                text: "x".into(),
+            },
+            SearchResult {
+                chunk_id: "2".into(),
+                path: "b.md".into(),
+                source: "memory_file".into(),
+                start_line: 1,
+                end_line: 2,
+                score: 0.5,
+                text: "y".into(),
+            },
+        ];
+        assert!(SearchResult::should_include_citations(
+            &results,
+            CitationMode::WhenMultipleSources
+        ));
+    }
+}
@@ -0,0 +1,61 @@
+//! Storage trait implemented by each memory persistence backend.
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::{config::MemoryConfig, search::SearchResult, types::MemoryChunk};
+
+/// Persistence for memory chunks and their embeddings.
+///
+/// Implementations: [`crate::store_sqlite::SqliteMemoryStore`],
+/// [`crate::store_postgres::PostgresMemoryStore`].
+#[async_trait]
+pub trait MemoryStore: Send + Sync {
+    /// Insert or replace a chunk.
+    async fn insert_chunk(&self, chunk: &MemoryChunk) -> Result<()>;
+
+    /// Remove every chunk indexed from `path`. Used to clear stale chunks
+    /// before re-indexing a modified or renamed file.
+    async fn delete_by_path(&self, path: &str) -> Result<()>;
+
+    /// Remove a single chunk by id.
+    async fn delete_chunk(&self, chunk_id: &str) -> Result<()>;
+
+    /// Fetch a single chunk by id.
+    async fn get_chunk(&self, chunk_id: &str) -> Result<Option<MemoryChunk>>;
+
+    /// Load every chunk, for in-process vector/keyword scoring.
+    async fn all_chunks(&self) -> Result<Vec<MemoryChunk>>;
+
+    /// Backend-native hybrid search, for stores that can combine the vector
+    /// and keyword halves more efficiently than in-process scoring over
+    /// [`Self::all_chunks`] (e.g. pushing the vector half down into a native
+    /// approximate-nearest-neighbor index). Returns `None` when the backend
+    /// has no specialized implementation, so the caller falls back to
+    /// in-process scoring.
+    async fn hybrid_search(
+        &self,
+        _query_embedding: &[f32],
+        _query_text: &str,
+        _vector_weight: f32,
+        _keyword_weight: f32,
+        _limit: usize,
+    ) -> Result<Option<Vec<SearchResult>>> {
+        Ok(None)
+    }
+}
+
+/// Construct a [`MemoryStore`] backend selected by [`MemoryConfig::database_url`]:
+/// Postgres/pgvector when set, SQLite (at `db_path`) otherwise. `dimensions`
+/// fixes the width of the pgvector embedding column to match the configured
+/// [`crate::embeddings::EmbeddingProvider`]; it's ignored for SQLite.
+pub async fn open_store(config: &MemoryConfig, dimensions: usize) -> Result<Box<dyn MemoryStore>> {
+    if let Some(database_url) = &config.database_url {
+        let store = crate::store_postgres::PostgresMemoryStore::new(database_url, dimensions).await?;
+        Ok(Box::new(store))
+    } else {
+        let pool = sqlx::SqlitePool::connect(&config.db_path).await?;
+        crate::schema::run_migrations(&pool).await?;
+        Ok(Box::new(crate::store_sqlite::SqliteMemoryStore::new(pool)))
+    }
+}
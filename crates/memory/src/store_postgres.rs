@@ -0,0 +1,290 @@
+//! Postgres/pgvector-backed memory store, for agents that need memory
+//! shared across many processes or hosts instead of a single local SQLite
+//! file.
+//!
+//! Unlike [`crate::store_sqlite::SqliteMemoryStore`], which scores
+//! `all_chunks` in-process, this backend pushes the vector half of hybrid
+//! search down into pgvector's approximate-nearest-neighbor index and the
+//! keyword half into Postgres full-text search, combining both in a single
+//! query (see [`PostgresMemoryStore::hybrid_search`]).
+
+use {
+    anyhow::{Context, Result},
+    async_trait::async_trait,
+    sqlx::{PgPool, Row, postgres::PgPoolOptions},
+};
+
+use crate::{search::SearchResult, store::MemoryStore, types::MemoryChunk};
+
+/// PostgreSQL/pgvector-backed persistence for memory chunks.
+pub struct PostgresMemoryStore {
+    pool: PgPool,
+}
+
+impl PostgresMemoryStore {
+    /// Create a new store and run migrations. `dimensions` fixes the width
+    /// of the `embedding` vector column to match the configured
+    /// [`crate::embeddings::EmbeddingProvider`].
+    pub async fn new(database_url: &str, dimensions: usize) -> Result<Self> {
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(database_url)
+            .await
+            .context("failed to connect to PostgreSQL")?;
+
+        sqlx::query("CREATE EXTENSION IF NOT EXISTS vector")
+            .execute(&pool)
+            .await
+            .context("failed to enable pgvector extension")?;
+
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS memory_chunks (
+                id TEXT PRIMARY KEY,
+                path TEXT NOT NULL,
+                source TEXT NOT NULL,
+                start_line INTEGER NOT NULL,
+                end_line INTEGER NOT NULL,
+                text TEXT NOT NULL,
+                embedding vector({dimensions}) NOT NULL
+            )"
+        ))
+        .execute(&pool)
+        .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_memory_chunks_path ON memory_chunks(path)")
+            .execute(&pool)
+            .await?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_memory_chunks_embedding
+             ON memory_chunks USING ivfflat (embedding vector_cosine_ops)",
+        )
+        .execute(&pool)
+        .await?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_memory_chunks_text_fts
+             ON memory_chunks USING gin (to_tsvector('english', text))",
+        )
+        .execute(&pool)
+        .await?;
+
+        Ok(Self { pool })
+    }
+}
+
+/// Render an embedding as pgvector's `[v1,v2,...]` text input format.
+fn encode_embedding(embedding: &[f32]) -> String {
+    let mut s = String::from("[");
+    for (i, v) in embedding.iter().enumerate() {
+        if i > 0 {
+            s.push(',');
+        }
+        s.push_str(&v.to_string());
+    }
+    s.push(']');
+    s
+}
+
+fn decode_embedding(s: &str) -> Vec<f32> {
+    s.trim_matches(|c| c == '[' || c == ']')
+        .split(',')
+        .filter(|p| !p.is_empty())
+        .filter_map(|p| p.parse::<f32>().ok())
+        .collect()
+}
+
+fn row_to_chunk(row: sqlx::postgres::PgRow) -> MemoryChunk {
+    MemoryChunk {
+        id: row.get("id"),
+        path: row.get("path"),
+        source: row.get("source"),
+        start_line: row.get::<i32, _>("start_line") as u32,
+        end_line: row.get::<i32, _>("end_line") as u32,
+        text: row.get("text"),
+        embedding: decode_embedding(&row.get::<String, _>("embedding")),
+    }
+}
+
+#[async_trait]
+impl MemoryStore for PostgresMemoryStore {
+    async fn insert_chunk(&self, chunk: &MemoryChunk) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO memory_chunks (id, path, source, start_line, end_line, text, embedding)
+             VALUES ($1, $2, $3, $4, $5, $6, $7::vector)
+             ON CONFLICT(id) DO UPDATE SET
+                path = excluded.path,
+                source = excluded.source,
+                start_line = excluded.start_line,
+                end_line = excluded.end_line,
+                text = excluded.text,
+                embedding = excluded.embedding",
+        )
+        .bind(&chunk.id)
+        .bind(&chunk.path)
+        .bind(&chunk.source)
+        .bind(chunk.start_line as i32)
+        .bind(chunk.end_line as i32)
+        .bind(&chunk.text)
+        .bind(encode_embedding(&chunk.embedding))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_by_path(&self, path: &str) -> Result<()> {
+        sqlx::query("DELETE FROM memory_chunks WHERE path = $1")
+            .bind(path)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_chunk(&self, chunk_id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM memory_chunks WHERE id = $1")
+            .bind(chunk_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_chunk(&self, chunk_id: &str) -> Result<Option<MemoryChunk>> {
+        let row = sqlx::query(
+            "SELECT id, path, source, start_line, end_line, text, embedding::text AS embedding
+             FROM memory_chunks WHERE id = $1",
+        )
+        .bind(chunk_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(row_to_chunk))
+    }
+
+    async fn all_chunks(&self) -> Result<Vec<MemoryChunk>> {
+        let rows = sqlx::query(
+            "SELECT id, path, source, start_line, end_line, text, embedding::text AS embedding
+             FROM memory_chunks",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(row_to_chunk).collect())
+    }
+
+    /// Orders by `vector_weight * cosine_similarity + keyword_weight * ts_rank`
+    /// directly in the database, so only the top `limit` rows ever leave
+    /// Postgres.
+    async fn hybrid_search(
+        &self,
+        query_embedding: &[f32],
+        query_text: &str,
+        vector_weight: f32,
+        keyword_weight: f32,
+        limit: usize,
+    ) -> Result<Option<Vec<SearchResult>>> {
+        let rows = sqlx::query(
+            "SELECT id, path, source, start_line, end_line, text,
+                    1 - (embedding <=> $1::vector) AS vector_score,
+                    ts_rank(to_tsvector('english', text), plainto_tsquery('english', $2)) AS kw_score
+             FROM memory_chunks
+             ORDER BY ($3 * (1 - (embedding <=> $1::vector)))
+                    + ($4 * ts_rank(to_tsvector('english', text), plainto_tsquery('english', $2))) DESC
+             LIMIT $5",
+        )
+        .bind(encode_embedding(query_embedding))
+        .bind(query_text)
+        .bind(vector_weight)
+        .bind(keyword_weight)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let results = rows
+            .into_iter()
+            .map(|row| {
+                let vector_score: f64 = row.get("vector_score");
+                let kw_score: f64 = row.get("kw_score");
+                SearchResult {
+                    chunk_id: row.get("id"),
+                    path: row.get("path"),
+                    source: row.get("source"),
+                    start_line: row.get::<i32, _>("start_line") as u32,
+                    end_line: row.get::<i32, _>("end_line") as u32,
+                    score: vector_weight * vector_score as f32 + keyword_weight * kw_score as f32,
+                    text: row.get("text"),
+                }
+            })
+            .collect();
+
+        Ok(Some(results))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MemoryChunk;
+
+    fn make_chunk(id: &str, path: &str) -> MemoryChunk {
+        MemoryChunk {
+            id: id.into(),
+            path: path.into(),
+            source: "memory_file".into(),
+            start_line: 1,
+            end_line: 2,
+            text: "hello world".into(),
+            embedding: vec![1.0, 0.0, 0.5],
+        }
+    }
+
+    // Requires a reachable Postgres instance with pgvector installed; run
+    // with `TEST_DATABASE_URL` set.
+    async fn make_store() -> Option<PostgresMemoryStore> {
+        let url = std::env::var("TEST_DATABASE_URL").ok()?;
+        Some(PostgresMemoryStore::new(&url, 3).await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_postgres_insert_and_get() {
+        let Some(store) = make_store().await else {
+            return;
+        };
+        store.insert_chunk(&make_chunk("pg-1", "MEMORY.md")).await.unwrap();
+
+        let chunk = store.get_chunk("pg-1").await.unwrap().unwrap();
+        assert_eq!(chunk.path, "MEMORY.md");
+        assert_eq!(chunk.embedding, vec![1.0, 0.0, 0.5]);
+    }
+
+    #[tokio::test]
+    async fn test_postgres_hybrid_search_orders_by_combined_score() {
+        let Some(store) = make_store().await else {
+            return;
+        };
+        store
+            .insert_chunk(&MemoryChunk {
+                text: "rust memory safety notes".into(),
+                embedding: vec![1.0, 0.0, 0.0],
+                ..make_chunk("pg-2", "memory/a.md")
+            })
+            .await
+            .unwrap();
+        store
+            .insert_chunk(&MemoryChunk {
+                text: "unrelated cooking content".into(),
+                embedding: vec![0.0, 1.0, 0.0],
+                ..make_chunk("pg-3", "memory/b.md")
+            })
+            .await
+            .unwrap();
+
+        let results = store
+            .hybrid_search(&[1.0, 0.0, 0.0], "rust memory", 0.5, 0.5, 5)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(!results.is_empty());
+        assert_eq!(results[0].chunk_id, "pg-2");
+    }
+}
@@ -0,0 +1,178 @@
+//! SQLite-backed memory store.
+//!
+//! Vector scoring is done in-process (cosine similarity over `all_chunks`)
+//! rather than in the database, since SQLite has no native vector type; see
+//! [`crate::store_postgres`] for the pgvector backend that pushes this down
+//! into the database instead.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{Row, SqlitePool};
+
+use crate::{store::MemoryStore, types::MemoryChunk};
+
+/// SQLite-backed persistence for memory chunks.
+pub struct SqliteMemoryStore {
+    pool: SqlitePool,
+}
+
+impl SqliteMemoryStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+fn encode_embedding(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn decode_embedding(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+#[async_trait]
+impl MemoryStore for SqliteMemoryStore {
+    async fn insert_chunk(&self, chunk: &MemoryChunk) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO memory_chunks (id, path, source, start_line, end_line, text, embedding)
+             VALUES (?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET
+                path = excluded.path,
+                source = excluded.source,
+                start_line = excluded.start_line,
+                end_line = excluded.end_line,
+                text = excluded.text,
+                embedding = excluded.embedding",
+        )
+        .bind(&chunk.id)
+        .bind(&chunk.path)
+        .bind(&chunk.source)
+        .bind(chunk.start_line as i64)
+        .bind(chunk.end_line as i64)
+        .bind(&chunk.text)
+        .bind(encode_embedding(&chunk.embedding))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_by_path(&self, path: &str) -> Result<()> {
+        sqlx::query("DELETE FROM memory_chunks WHERE path = ?")
+            .bind(path)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_chunk(&self, chunk_id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM memory_chunks WHERE id = ?")
+            .bind(chunk_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_chunk(&self, chunk_id: &str) -> Result<Option<MemoryChunk>> {
+        let row = sqlx::query(
+            "SELECT id, path, source, start_line, end_line, text, embedding
+             FROM memory_chunks WHERE id = ?",
+        )
+        .bind(chunk_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| MemoryChunk {
+            id: row.get("id"),
+            path: row.get("path"),
+            source: row.get("source"),
+            start_line: row.get::<i64, _>("start_line") as u32,
+            end_line: row.get::<i64, _>("end_line") as u32,
+            text: row.get("text"),
+            embedding: decode_embedding(&row.get::<Vec<u8>, _>("embedding")),
+        }))
+    }
+
+    async fn all_chunks(&self) -> Result<Vec<MemoryChunk>> {
+        let rows = sqlx::query("SELECT id, path, source, start_line, end_line, text, embedding FROM memory_chunks")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| MemoryChunk {
+                id: row.get("id"),
+                path: row.get("path"),
+                source: row.get("source"),
+                start_line: row.get::<i64, _>("start_line") as u32,
+                end_line: row.get::<i64, _>("end_line") as u32,
+                text: row.get("text"),
+                embedding: decode_embedding(&row.get::<Vec<u8>, _>("embedding")),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::run_migrations;
+
+    async fn make_store() -> SqliteMemoryStore {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        SqliteMemoryStore::new(pool)
+    }
+
+    fn make_chunk(id: &str, path: &str) -> MemoryChunk {
+        MemoryChunk {
+            id: id.into(),
+            path: path.into(),
+            source: "memory_file".into(),
+            start_line: 1,
+            end_line: 2,
+            text: "hello world".into(),
+            embedding: vec![1.0, 0.0, 0.5],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_insert_and_get() {
+        let store = make_store().await;
+        store.insert_chunk(&make_chunk("1", "MEMORY.md")).await.unwrap();
+
+        let chunk = store.get_chunk("1").await.unwrap().unwrap();
+        assert_eq!(chunk.path, "MEMORY.md");
+        assert_eq!(chunk.embedding, vec![1.0, 0.0, 0.5]);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_delete_by_path() {
+        let store = make_store().await;
+        store.insert_chunk(&make_chunk("1", "memory/a.md")).await.unwrap();
+        store.insert_chunk(&make_chunk("2", "memory/a.md")).await.unwrap();
+        store.insert_chunk(&make_chunk("3", "memory/b.md")).await.unwrap();
+
+        store.delete_by_path("memory/a.md").await.unwrap();
+
+        let remaining = store.all_chunks().await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].path, "memory/b.md");
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_upsert_replaces() {
+        let store = make_store().await;
+        store.insert_chunk(&make_chunk("1", "MEMORY.md")).await.unwrap();
+
+        let mut updated = make_chunk("1", "MEMORY.md");
+        updated.text = "updated text".into();
+        store.insert_chunk(&updated).await.unwrap();
+
+        let chunks = store.all_chunks().await.unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].text, "updated text");
+    }
+}
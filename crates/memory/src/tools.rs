@@ -1,5 +1,5 @@
 /// Agent tools for memory search, retrieval, and persistence.
-use std::sync::Arc;
+use std::{io::Read, sync::Arc};
 
 use {async_trait::async_trait, moltis_agents::tool_registry::AgentTool, serde_json::json};
 
@@ -201,6 +201,710 @@ impl AgentTool for MemorySaveTool {
     }
 }
 
+/// Tool: fetch a web page, strip boilerplate, and ingest it as memory chunks.
+pub struct FetchUrlTool {
+    manager: Arc<MemoryManager>,
+}
+
+impl FetchUrlTool {
+    pub fn new(manager: Arc<MemoryManager>) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl AgentTool for FetchUrlTool {
+    fn name(&self) -> &str {
+        "fetch_url"
+    }
+
+    fn description(&self) -> &str {
+        "Fetch a web page, strip boilerplate HTML down to the main article text, and ingest it as memory chunks retrievable via memory_search/memory_get."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "url": {
+                    "type": "string",
+                    "description": "The web page URL to fetch and ingest"
+                }
+            },
+            "required": ["url"]
+        })
+    }
+
+    async fn execute(&self, params: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let url = params["url"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("missing 'url' parameter"))?;
+
+        let response = reqwest::get(url).await?.error_for_status()?;
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+            .to_string();
+
+        if !is_textual_content_type(&content_type) {
+            anyhow::bail!(
+                "fetch_url only supports textual content; got Content-Type: {content_type:?}"
+            );
+        }
+
+        let body = response.text().await?;
+        let text = if content_type.contains("html") {
+            crate::html_extract::extract_readable_text(&body)
+        } else {
+            body
+        };
+
+        let chunk_ids = self.manager.ingest_text(url, "web_fetch", &text).await?;
+
+        Ok(json!({ "url": url, "chunk_ids": chunk_ids, "chunks": chunk_ids.len() }))
+    }
+}
+
+/// Whether a raw `Content-Type` header value is safe to read via
+/// `reqwest::Response::text`. Anything else must be read as `.bytes()`
+/// instead — calling `.text()` on binary content silently corrupts it into
+/// garbage unicode.
+fn is_textual_content_type(content_type: &str) -> bool {
+    let base = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_lowercase();
+    base.starts_with("text/")
+        || matches!(
+            base.as_str(),
+            "application/json" | "application/xml" | "application/xhtml+xml" | "application/javascript"
+        )
+}
+
+/// `User-Agent` crates.io requires on API requests; an anonymous client gets
+/// a 403 without one.
+const CRATES_IO_USER_AGENT: &str = "moltis-memory (fetch_crate tool)";
+
+/// Tool: download a published crate's source tarball from crates.io and
+/// ingest its source as memory chunks.
+pub struct FetchCrateTool {
+    manager: Arc<MemoryManager>,
+}
+
+impl FetchCrateTool {
+    pub fn new(manager: Arc<MemoryManager>) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl AgentTool for FetchCrateTool {
+    fn name(&self) -> &str {
+        "fetch_crate"
+    }
+
+    fn description(&self) -> &str {
+        "Download a published crate's source tarball from crates.io and ingest its .rs, Cargo.toml, and README files as memory chunks keyed by '<name>-<version>/<in-archive path>', retrievable via memory_search/memory_get. Omit 'version' to use the crate's latest non-yanked release."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Crate name on crates.io"
+                },
+                "version": {
+                    "type": "string",
+                    "description": "Crate version to fetch; defaults to the latest non-yanked release"
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    async fn execute(&self, params: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let name = params["name"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("missing 'name' parameter"))?;
+        let version = match params["version"].as_str() {
+            Some(v) => v.to_string(),
+            None => latest_non_yanked_version(name).await?,
+        };
+
+        // The download endpoint 302s to a static CDN URL; reqwest follows
+        // redirects by default, so a plain GET is enough.
+        let url = format!("https://crates.io/api/v1/crates/{name}/{version}/download");
+        let bytes = reqwest::Client::new()
+            .get(&url)
+            .header(reqwest::header::USER_AGENT, CRATES_IO_USER_AGENT)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+
+        let decoder = flate2::read::GzDecoder::new(bytes.as_ref());
+        let mut archive = tar::Archive::new(decoder);
+
+        let mut chunk_ids = Vec::new();
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let in_archive_path = entry.path()?.to_string_lossy().into_owned();
+            if !is_ingestible_crate_file(&in_archive_path) {
+                continue;
+            }
+
+            let mut text = String::new();
+            if entry.read_to_string(&mut text).is_err() {
+                continue; // not UTF-8 text; skip
+            }
+
+            let key = format!("{name}-{version}/{in_archive_path}");
+            let ids = self.manager.ingest_text(&key, "crate_source", &text).await?;
+            chunk_ids.extend(ids);
+        }
+
+        Ok(json!({
+            "name": name,
+            "version": version,
+            "chunk_ids": chunk_ids,
+            "chunks": chunk_ids.len(),
+        }))
+    }
+}
+
+/// Whether an in-archive path from a `.crate` tarball is worth ingesting:
+/// Rust source, the manifest, or a README at any depth.
+fn is_ingestible_crate_file(path: &str) -> bool {
+    path.ends_with(".rs")
+        || path
+            .rsplit('/')
+            .next()
+            .is_some_and(|f| f == "Cargo.toml" || f.starts_with("README"))
+}
+
+/// Query crates.io for `name`'s latest non-yanked version. The summary
+/// endpoint returns `versions` newest-first, so the first non-yanked entry
+/// is the one to fetch.
+async fn latest_non_yanked_version(name: &str) -> anyhow::Result<String> {
+    let url = format!("https://crates.io/api/v1/crates/{name}");
+    let body: serde_json::Value = reqwest::Client::new()
+        .get(&url)
+        .header(reqwest::header::USER_AGENT, CRATES_IO_USER_AGENT)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    body["versions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|v| !v["yanked"].as_bool().unwrap_or(false))
+        .and_then(|v| v["num"].as_str())
+        .map(String::from)
+        .ok_or_else(|| anyhow::anyhow!("no non-yanked version found for crate '{name}'"))
+}
+
+/// Tool: bulk-index a directory tree into memory, respecting `.gitignore`.
+pub struct MemoryCrawlTool {
+    manager: Arc<MemoryManager>,
+}
+
+impl MemoryCrawlTool {
+    pub fn new(manager: Arc<MemoryManager>) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl AgentTool for MemoryCrawlTool {
+    fn name(&self) -> &str {
+        "memory_crawl"
+    }
+
+    fn description(&self) -> &str {
+        "Recursively index a directory tree into memory, respecting .gitignore/.ignore rules. Use this to make a codebase or docs folder searchable via memory_search."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "root": {
+                    "type": "string",
+                    "description": "Directory to crawl, relative to the memory data directory"
+                },
+                "all_files": {
+                    "type": "boolean",
+                    "description": "Index every file, not just ones matching the configured extension allow-list",
+                    "default": false
+                },
+                "max_files": {
+                    "type": "integer",
+                    "description": "Maximum number of files to index in this call",
+                    "default": 20
+                }
+            },
+            "required": ["root"]
+        })
+    }
+
+    async fn execute(&self, params: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let root = params["root"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("missing 'root' parameter"))?;
+        let all_files = params["all_files"].as_bool().unwrap_or(false);
+        let max_files = params["max_files"].as_u64().unwrap_or(20) as usize;
+
+        let summary = self.manager.crawl(root, all_files, max_files).await?;
+
+        Ok(json!({
+            "indexed": summary.indexed,
+            "skipped": summary.skipped,
+            "chunks": summary.chunks,
+        }))
+    }
+}
+
+/// Tool: list the immediate entries of a directory for an agent to browse
+/// before deciding what to [`MemoryManager::ingest_dir`].
+pub struct ListDirTool {
+    manager: Arc<MemoryManager>,
+}
+
+impl ListDirTool {
+    pub fn new(manager: Arc<MemoryManager>) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl AgentTool for ListDirTool {
+    fn name(&self) -> &str {
+        "list_dir"
+    }
+
+    fn description(&self) -> &str {
+        "List the immediate entries of a directory (relative to the memory data directory), so an agent can browse a local corpus before calling ingest_dir. Directory names are suffixed with '/'."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Directory to list, relative to the memory data directory"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(&self, params: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let path = params["path"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("missing 'path' parameter"))?;
+
+        let entries = self.manager.list_dir(path)?;
+
+        Ok(json!({
+            "path": path,
+            "entries": entries.iter().map(|e| json!({
+                "name": e.name,
+                "path_type": e.path_type,
+                "size": e.size,
+                "mtime": e.mtime,
+            })).collect::<Vec<_>>(),
+        }))
+    }
+}
+
+/// Tool: recursively ingest a directory tree's text files as memory chunks.
+pub struct IngestDirTool {
+    manager: Arc<MemoryManager>,
+}
+
+impl IngestDirTool {
+    pub fn new(manager: Arc<MemoryManager>) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl AgentTool for IngestDirTool {
+    fn name(&self) -> &str {
+        "ingest_dir"
+    }
+
+    fn description(&self) -> &str {
+        "Recursively walk a directory (relative to the memory data directory), reading each text file matching 'extensions' and saving its contents as chunks keyed by relative path, retrievable with memory_get. Unlike memory_crawl, this does not respect .gitignore — point it at a known local corpus, e.g. all '.md' files under 'docs/'."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "root": {
+                    "type": "string",
+                    "description": "Directory to ingest, relative to the memory data directory"
+                },
+                "extensions": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "File extensions to include, without the leading dot (e.g. [\"md\", \"txt\"]). Every file is ingested when empty.",
+                    "default": []
+                },
+                "max_depth": {
+                    "type": "integer",
+                    "description": "Maximum number of directory levels to descend",
+                    "default": 5
+                }
+            },
+            "required": ["root"]
+        })
+    }
+
+    async fn execute(&self, params: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let root = params["root"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("missing 'root' parameter"))?;
+        let extensions: Vec<String> = params["extensions"]
+            .as_array()
+            .map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+        let max_depth = params["max_depth"].as_u64().unwrap_or(5) as usize;
+
+        let summary = self.manager.ingest_dir(root, &extensions, max_depth).await?;
+
+        Ok(json!({
+            "indexed": summary.indexed,
+            "skipped": summary.skipped,
+            "chunks": summary.chunks,
+        }))
+    }
+}
+
+/// Tool: remove a memory chunk, and optionally its entire source file.
+pub struct MemoryDeleteTool {
+    manager: Arc<MemoryManager>,
+}
+
+impl MemoryDeleteTool {
+    pub fn new(manager: Arc<MemoryManager>) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl AgentTool for MemoryDeleteTool {
+    fn name(&self) -> &str {
+        "memory_delete"
+    }
+
+    fn description(&self) -> &str {
+        "Remove a memory chunk by its ID. Set delete_file to also delete the chunk's source file from disk and clear every other chunk indexed from it."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "chunk_id": {
+                    "type": "string",
+                    "description": "The chunk ID to delete"
+                },
+                "delete_file": {
+                    "type": "boolean",
+                    "description": "Also delete the chunk's source file and all of its chunks",
+                    "default": false
+                }
+            },
+            "required": ["chunk_id"]
+        })
+    }
+
+    async fn execute(&self, params: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let chunk_id = params["chunk_id"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("missing 'chunk_id' parameter"))?;
+        let delete_file = params["delete_file"].as_bool().unwrap_or(false);
+
+        let outcome = self.manager.delete_chunk(chunk_id, delete_file).await?;
+
+        Ok(json!({
+            "found": outcome.found,
+            "path": outcome.path,
+            "file_deleted": outcome.file_deleted,
+        }))
+    }
+}
+
+/// Tool: surgically revise a memory file via deletes, replaces, and adds.
+pub struct MemoryEditTool {
+    manager: Arc<MemoryManager>,
+}
+
+impl MemoryEditTool {
+    pub fn new(manager: Arc<MemoryManager>) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl AgentTool for MemoryEditTool {
+    fn name(&self) -> &str {
+        "memory_edit"
+    }
+
+    fn description(&self) -> &str {
+        "Surgically revise a memory file: remove content matching each of 'deletes', apply each {match, replacement} in 'replaces', then append each entry in 'adds' — in that order. Re-indexes the file afterward so searches reflect the change immediately."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "file": {
+                    "type": "string",
+                    "description": "Target file: MEMORY.md, memory.md, or memory/<name>.md"
+                },
+                "deletes": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Substrings or headings to remove, applied first",
+                    "default": []
+                },
+                "replaces": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "match": { "type": "string" },
+                            "replacement": { "type": "string" }
+                        },
+                        "required": ["match", "replacement"]
+                    },
+                    "description": "Substring replacements, applied after deletes",
+                    "default": []
+                },
+                "adds": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "New content blocks to append, applied last",
+                    "default": []
+                }
+            },
+            "required": ["file"]
+        })
+    }
+
+    async fn execute(&self, params: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let file = params["file"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("missing 'file' parameter"))?;
+
+        let deletes: Vec<String> = params["deletes"]
+            .as_array()
+            .map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+
+        let replaces: Vec<(String, String)> = params["replaces"]
+            .as_array()
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| {
+                        let m = v["match"].as_str()?;
+                        let r = v["replacement"].as_str()?;
+                        Some((m.to_string(), r.to_string()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let adds: Vec<String> = params["adds"]
+            .as_array()
+            .map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+
+        let outcome = self
+            .manager
+            .edit_file(file, &deletes, &replaces, &adds)
+            .await?;
+
+        Ok(json!({
+            "file": file,
+            "bytes_before": outcome.bytes_before,
+            "bytes_after": outcome.bytes_after,
+            "bytes_changed": outcome.bytes_after as i64 - outcome.bytes_before as i64,
+            "chunks": outcome.chunks,
+        }))
+    }
+}
+
+/// Tool: one-call retrieval-augmented context builder.
+pub struct MemoryAnswerTool {
+    manager: Arc<MemoryManager>,
+}
+
+impl MemoryAnswerTool {
+    pub fn new(manager: Arc<MemoryManager>) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl AgentTool for MemoryAnswerTool {
+    fn name(&self) -> &str {
+        "memory_answer"
+    }
+
+    fn description(&self) -> &str {
+        "Search memory and assemble the results into a single ready-to-prompt context string with inline citations, instead of manually calling memory_search then memory_get for each chunk."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "The search query"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of chunks to retrieve before merging",
+                    "default": 5
+                },
+                "max_context_chars": {
+                    "type": "integer",
+                    "description": "Character budget for the assembled context; lowest-scoring passages are dropped first",
+                    "default": 4000
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn execute(&self, params: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let query = params["query"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("missing 'query' parameter"))?;
+        let limit = params["limit"].as_u64().unwrap_or(5) as usize;
+        let max_context_chars = params["max_context_chars"].as_u64().unwrap_or(4000) as usize;
+
+        let result = self.manager.answer(query, limit, max_context_chars).await?;
+
+        let sources: Vec<serde_json::Value> = result
+            .sources
+            .iter()
+            .map(|s| {
+                json!({
+                    "path": s.path,
+                    "start_line": s.start_line,
+                    "end_line": s.end_line,
+                    "score": s.score,
+                    "citation": format!("{}#{}", s.path, s.start_line),
+                })
+            })
+            .collect();
+
+        Ok(json!({ "context": result.context, "sources": sources }))
+    }
+}
+
+/// Tool: force a targeted re-sync of a single path on demand.
+pub struct MemoryReindexTool {
+    manager: Arc<MemoryManager>,
+}
+
+impl MemoryReindexTool {
+    pub fn new(manager: Arc<MemoryManager>) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl AgentTool for MemoryReindexTool {
+    fn name(&self) -> &str {
+        "memory_reindex"
+    }
+
+    fn description(&self) -> &str {
+        "Force a re-index of a single file, relative to the memory data directory. Use this after an external change the watcher hasn't picked up yet, or to confirm a file's chunks are current."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "File to re-index, relative to the memory data directory"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(&self, params: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let path = params["path"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("missing 'path' parameter"))?;
+
+        let chunks = self.manager.reindex(path).await?;
+
+        Ok(json!({ "path": path, "chunks": chunks }))
+    }
+}
+
+/// Tool: check every stored chunk against the content-addressing manifest.
+pub struct VerifyStoreTool {
+    manager: Arc<MemoryManager>,
+}
+
+impl VerifyStoreTool {
+    pub fn new(manager: Arc<MemoryManager>) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl AgentTool for VerifyStoreTool {
+    fn name(&self) -> &str {
+        "verify_store"
+    }
+
+    fn description(&self) -> &str {
+        "Check every stored chunk's SHA-256 hash against the recorded manifest, verifying the manifest's ed25519 signature first when a trusted public key is configured. Reports tampered chunks (hash mismatch), chunks missing from the manifest, and manifest entries with no corresponding chunk."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({ "type": "object", "properties": {} })
+    }
+
+    async fn execute(&self, _params: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let report = self.manager.verify_store().await?;
+
+        Ok(json!({
+            "clean": report.is_clean(),
+            "checked": report.checked,
+            "tampered": report.tampered,
+            "missing_from_manifest": report.missing_from_manifest,
+            "missing_chunks": report.missing_chunks,
+            "signature_verified": report.signature_verified,
+        }))
+    }
+}
+
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 #[cfg(test)]
 mod tests {
@@ -214,504 +918,1188 @@ mod tests {
         tempfile::TempDir,
     };
 
-    /// Same keyword-based mock embedder used in manager tests.
-    const KEYWORDS: [&str; 8] = [
-        "rust", "python", "database", "memory", "search", "network", "cooking", "music",
-    ];
+    /// Keyword-based mock embedder: one dimension per entry in `KEYWORDS`.
+    const KEYWORDS: [&str; 8] = [
+        "rust", "python", "database", "memory", "search", "network", "cooking", "music",
+    ];
+
+    struct MockEmbedder;
+
+    #[async_trait]
+    impl EmbeddingProvider for MockEmbedder {
+        async fn embed(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+            let lower = text.to_lowercase();
+            Ok(KEYWORDS
+                .iter()
+                .map(|kw| {
+                    if lower.contains(kw) {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                })
+                .collect())
+        }
+
+        fn model_name(&self) -> &str {
+            "mock-model"
+        }
+
+        fn dimensions(&self) -> usize {
+            8
+        }
+
+        fn provider_key(&self) -> &str {
+            "mock"
+        }
+    }
+
+    /// Set up a memory manager in a temporary directory.
+    ///
+    /// Returns the Arc'd manager and the TempDir handle; the data_dir is
+    /// `tmp.path()` — the root for MEMORY.md and memory/.
+    async fn setup_manager() -> (Arc<MemoryManager>, TempDir) {
+        let tmp = TempDir::new().unwrap();
+        let data_dir = tmp.path().to_path_buf();
+        let mem_dir = data_dir.join("memory");
+        std::fs::create_dir_all(&mem_dir).unwrap();
+
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        run_migrations(&pool).await.unwrap();
+
+        let config = MemoryConfig {
+            db_path: ":memory:".into(),
+            data_dir: Some(data_dir),
+            memory_dirs: vec![tmp.path().join("MEMORY.md"), mem_dir],
+            chunk_size: 50,
+            chunk_overlap: 10,
+            vector_weight: 0.7,
+            keyword_weight: 0.3,
+            ..Default::default()
+        };
+
+        let store = Box::new(SqliteMemoryStore::new(pool));
+        let embedder = Box::new(MockEmbedder);
+        let manager = Arc::new(MemoryManager::new(config, store, embedder));
+        (manager, tmp)
+    }
+
+    #[test]
+    fn test_memory_search_tool_schema() {
+        // Schema checks don't need a real manager — use a tokio runtime just to build one
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let (manager, _tmp) = rt.block_on(setup_manager());
+        let tool = MemorySearchTool::new(manager);
+        assert_eq!(tool.name(), "memory_search");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["query"].is_object());
+        assert!(
+            schema["required"]
+                .as_array()
+                .unwrap()
+                .contains(&json!("query"))
+        );
+    }
+
+    #[test]
+    fn test_memory_get_tool_schema() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let (manager, _tmp) = rt.block_on(setup_manager());
+        let tool = MemoryGetTool::new(manager);
+        assert_eq!(tool.name(), "memory_get");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["chunk_id"].is_object());
+        assert!(
+            schema["required"]
+                .as_array()
+                .unwrap()
+                .contains(&json!("chunk_id"))
+        );
+    }
+
+    /// Execute memory_search via the tool interface and verify JSON output structure.
+    #[tokio::test]
+    async fn test_memory_search_tool_execute() {
+        let (manager, tmp) = setup_manager().await;
+        let mem_dir = tmp.path().join("memory");
+
+        std::fs::write(
+            mem_dir.join("note.md"),
+            "Rust is a systems programming language with great memory safety.",
+        )
+        .unwrap();
+
+        manager.sync().await.unwrap();
+
+        let tool = MemorySearchTool::new(manager);
+        let result = tool
+            .execute(json!({ "query": "rust memory", "limit": 3 }))
+            .await
+            .unwrap();
+
+        // Verify JSON structure
+        let results = result["results"].as_array().unwrap();
+        assert!(!results.is_empty(), "execute should return results");
+
+        let first = &results[0];
+        assert!(first["chunk_id"].is_string());
+        assert!(first["path"].is_string());
+        assert!(first["score"].is_f64());
+        assert!(first["text"].is_string());
+        assert!(first["start_line"].is_number());
+        assert!(first["end_line"].is_number());
+
+        // The text should contain what we wrote
+        let text = first["text"].as_str().unwrap();
+        assert!(
+            text.contains("Rust"),
+            "search result text should contain 'Rust', got: {text}"
+        );
+    }
+
+    /// Execute memory_search with missing query — should return an error.
+    #[tokio::test]
+    async fn test_memory_search_tool_missing_query() {
+        let (manager, _tmp) = setup_manager().await;
+        let tool = MemorySearchTool::new(manager);
+        let result = tool.execute(json!({})).await;
+        assert!(result.is_err(), "missing query should produce an error");
+    }
+
+    /// Execute memory_get for an existing chunk.
+    #[tokio::test]
+    async fn test_memory_get_tool_execute() {
+        let (manager, tmp) = setup_manager().await;
+        let mem_dir = tmp.path().join("memory");
+
+        std::fs::write(mem_dir.join("data.md"), "Some database content here.").unwrap();
+        manager.sync().await.unwrap();
+
+        // First search to find a chunk_id
+        let search_tool = MemorySearchTool::new(Arc::clone(&manager));
+        let search_result = search_tool
+            .execute(json!({ "query": "database", "limit": 1 }))
+            .await
+            .unwrap();
+        let chunk_id = search_result["results"][0]["chunk_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        // Now get that chunk
+        let get_tool = MemoryGetTool::new(manager);
+        let result = get_tool
+            .execute(json!({ "chunk_id": chunk_id }))
+            .await
+            .unwrap();
+
+        assert!(result["error"].is_null(), "should not have error");
+        assert_eq!(result["chunk_id"].as_str().unwrap(), chunk_id);
+        let text = result["text"].as_str().unwrap();
+        assert!(
+            text.contains("database"),
+            "retrieved chunk should contain 'database', got: {text}"
+        );
+    }
+
+    /// Execute memory_get for a non-existent chunk — should return error JSON (not a Rust error).
+    #[tokio::test]
+    async fn test_memory_get_tool_not_found() {
+        let (manager, _tmp) = setup_manager().await;
+        let tool = MemoryGetTool::new(manager);
+        let result = tool
+            .execute(json!({ "chunk_id": "nonexistent-id" }))
+            .await
+            .unwrap();
+
+        assert_eq!(result["error"].as_str().unwrap(), "chunk not found");
+        assert_eq!(result["chunk_id"].as_str().unwrap(), "nonexistent-id");
+    }
+
+    /// Execute memory_get with missing chunk_id — should return an error.
+    #[tokio::test]
+    async fn test_memory_get_tool_missing_param() {
+        let (manager, _tmp) = setup_manager().await;
+        let tool = MemoryGetTool::new(manager);
+        let result = tool.execute(json!({})).await;
+        assert!(result.is_err(), "missing chunk_id should produce an error");
+    }
+
+    /// Round-trip: sync → search via tool → get via tool → verify text matches.
+    #[tokio::test]
+    async fn test_tools_round_trip() {
+        let (manager, tmp) = setup_manager().await;
+        let mem_dir = tmp.path().join("memory");
+
+        let original_text = "Cooking pasta with fresh herbs and olive oil is a delight.";
+        std::fs::write(mem_dir.join("recipe.md"), original_text).unwrap();
+        manager.sync().await.unwrap();
+
+        let search_tool = MemorySearchTool::new(Arc::clone(&manager));
+        let get_tool = MemoryGetTool::new(Arc::clone(&manager));
+
+        // Search
+        let search_result = search_tool
+            .execute(json!({ "query": "cooking", "limit": 1 }))
+            .await
+            .unwrap();
+        let results = search_result["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        let chunk_id = results[0]["chunk_id"].as_str().unwrap();
+
+        // Get
+        let get_result = get_tool
+            .execute(json!({ "chunk_id": chunk_id }))
+            .await
+            .unwrap();
+        let retrieved_text = get_result["text"].as_str().unwrap();
 
-    struct MockEmbedder;
+        assert_eq!(
+            retrieved_text, original_text,
+            "round-trip text should match original"
+        );
+    }
 
-    #[async_trait]
-    impl EmbeddingProvider for MockEmbedder {
-        async fn embed(&self, text: &str) -> anyhow::Result<Vec<f32>> {
-            let lower = text.to_lowercase();
-            Ok(KEYWORDS
-                .iter()
-                .map(|kw| {
-                    if lower.contains(kw) {
-                        1.0
-                    } else {
-                        0.0
-                    }
-                })
-                .collect())
-        }
+    // ---- MemorySaveTool tests ----
 
-        fn model_name(&self) -> &str {
-            "mock-model"
-        }
+    #[test]
+    fn test_memory_save_tool_schema() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let (manager, _tmp) = rt.block_on(setup_manager());
+        let tool = MemorySaveTool::new(manager);
+        assert_eq!(tool.name(), "memory_save");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["content"].is_object());
+        assert!(schema["properties"]["file"].is_object());
+        assert!(schema["properties"]["append"].is_object());
+        assert!(
+            schema["required"]
+                .as_array()
+                .unwrap()
+                .contains(&json!("content"))
+        );
+    }
 
-        fn dimensions(&self) -> usize {
-            8
+    /// Default append mode: two writes produce both contents in the file.
+    #[tokio::test]
+    async fn test_memory_save_append_default() {
+        let (manager, tmp) = setup_manager().await;
+        let data_dir = tmp.path().to_path_buf();
+        let tool = MemorySaveTool::new(Arc::clone(&manager));
+
+        let r1 = tool
+            .execute(json!({ "content": "First memory about rust." }))
+            .await
+            .unwrap();
+        assert_eq!(r1["saved"], json!(true));
+        assert_eq!(r1["path"], json!("MEMORY.md"));
+
+        let r2 = tool
+            .execute(json!({ "content": "Second memory about database." }))
+            .await
+            .unwrap();
+        assert_eq!(r2["saved"], json!(true));
+
+        let content = std::fs::read_to_string(data_dir.join("MEMORY.md")).unwrap();
+        assert!(content.contains("First memory"), "should have first write");
+        assert!(
+            content.contains("Second memory"),
+            "should have second write"
+        );
+    }
+
+    /// Overwrite mode: second write replaces the first.
+    #[tokio::test]
+    async fn test_memory_save_overwrite() {
+        let (manager, tmp) = setup_manager().await;
+        let data_dir = tmp.path().to_path_buf();
+        let tool = MemorySaveTool::new(Arc::clone(&manager));
+
+        tool.execute(json!({ "content": "Original content about rust." }))
+            .await
+            .unwrap();
+
+        tool.execute(json!({
+            "content": "Replaced content about database.",
+            "append": false
+        }))
+        .await
+        .unwrap();
+
+        let content = std::fs::read_to_string(data_dir.join("MEMORY.md")).unwrap();
+        assert!(
+            !content.contains("Original"),
+            "overwrite should remove original"
+        );
+        assert!(content.contains("Replaced"), "overwrite should have new");
+    }
+
+    /// Custom file under memory/ subdirectory.
+    #[tokio::test]
+    async fn test_memory_save_custom_file() {
+        let (manager, tmp) = setup_manager().await;
+        let data_dir = tmp.path().to_path_buf();
+        let tool = MemorySaveTool::new(Arc::clone(&manager));
+
+        let result = tool
+            .execute(json!({
+                "content": "Notes from 2024-01-15 about cooking.",
+                "file": "memory/2024-01-15.md"
+            }))
+            .await
+            .unwrap();
+
+        assert_eq!(result["saved"], json!(true));
+        assert_eq!(result["path"], json!("memory/2024-01-15.md"));
+
+        let content =
+            std::fs::read_to_string(data_dir.join("memory").join("2024-01-15.md")).unwrap();
+        assert!(content.contains("Notes from 2024-01-15"));
+    }
+
+    /// Auto-creates memory/ directory if it doesn't exist.
+    #[tokio::test]
+    async fn test_memory_save_creates_memory_dir() {
+        let (manager, tmp) = setup_manager().await;
+        let data_dir = tmp.path().to_path_buf();
+        // Remove the memory dir that setup_manager created
+        std::fs::remove_dir_all(data_dir.join("memory")).unwrap();
+        assert!(!data_dir.join("memory").exists());
+
+        let tool = MemorySaveTool::new(Arc::clone(&manager));
+        tool.execute(json!({
+            "content": "Content for new dir.",
+            "file": "memory/notes.md"
+        }))
+        .await
+        .unwrap();
+
+        assert!(data_dir.join("memory").join("notes.md").exists());
+    }
+
+    /// Re-indexes after write so content is immediately searchable.
+    #[tokio::test]
+    async fn test_memory_save_reindexes() {
+        let (manager, _tmp) = setup_manager().await;
+        let save_tool = MemorySaveTool::new(Arc::clone(&manager));
+        let search_tool = MemorySearchTool::new(Arc::clone(&manager));
+
+        save_tool
+            .execute(json!({
+                "content": "The cooking recipe uses garlic and olive oil.",
+                "file": "memory/recipe.md"
+            }))
+            .await
+            .unwrap();
+
+        let results = search_tool
+            .execute(json!({ "query": "cooking", "limit": 5 }))
+            .await
+            .unwrap();
+
+        let items = results["results"].as_array().unwrap();
+        assert!(!items.is_empty(), "saved content should be searchable");
+        assert!(
+            items[0]["text"].as_str().unwrap().contains("cooking"),
+            "search should find the saved text"
+        );
+    }
+
+    /// Path traversal attempts are rejected.
+    #[tokio::test]
+    async fn test_memory_save_rejects_path_traversal() {
+        let (manager, _tmp) = setup_manager().await;
+        let tool = MemorySaveTool::new(Arc::clone(&manager));
+
+        for bad_path in &[
+            "../etc/passwd",
+            "memory/../../../etc/passwd",
+            "memory/../../secret.md",
+        ] {
+            let result = tool
+                .execute(json!({ "content": "test", "file": bad_path }))
+                .await;
+            assert!(result.is_err(), "should reject path traversal: {bad_path}");
         }
+    }
 
-        fn provider_key(&self) -> &str {
-            "mock"
+    /// Absolute paths are rejected.
+    #[tokio::test]
+    async fn test_memory_save_rejects_absolute_paths() {
+        let (manager, _tmp) = setup_manager().await;
+        let tool = MemorySaveTool::new(Arc::clone(&manager));
+
+        let result = tool
+            .execute(json!({ "content": "test", "file": "/etc/passwd" }))
+            .await;
+        assert!(result.is_err(), "should reject absolute paths");
+    }
+
+    /// Invalid file names are rejected.
+    #[tokio::test]
+    async fn test_memory_save_rejects_invalid_names() {
+        let (manager, _tmp) = setup_manager().await;
+        let tool = MemorySaveTool::new(Arc::clone(&manager));
+
+        let invalid = &[
+            "memory/notes.txt",     // wrong extension
+            "memory/.md",           // empty stem
+            "memory/a b c.md",      // spaces in name
+            "memory/sub/nested.md", // nested subdirectory
+            "random.md",            // not MEMORY.md or memory/
+            "foo/bar.md",           // not in allowed paths
+        ];
+
+        for name in invalid {
+            let result = tool
+                .execute(json!({ "content": "test", "file": name }))
+                .await;
+            assert!(result.is_err(), "should reject invalid name: {name}");
         }
     }
 
-    /// Set up a memory manager in a temporary directory.
-    ///
-    /// Returns the Arc'd manager, the TempDir handle, and the data_dir path
-    /// (which is `tmp.path()` — the root for MEMORY.md and memory/).
-    async fn setup_manager() -> (Arc<MemoryManager>, TempDir) {
-        let tmp = TempDir::new().unwrap();
-        let data_dir = tmp.path().to_path_buf();
-        let mem_dir = data_dir.join("memory");
-        std::fs::create_dir_all(&mem_dir).unwrap();
+    /// Missing content parameter returns an error.
+    #[tokio::test]
+    async fn test_memory_save_missing_content() {
+        let (manager, _tmp) = setup_manager().await;
+        let tool = MemorySaveTool::new(Arc::clone(&manager));
+
+        let result = tool.execute(json!({})).await;
+        assert!(result.is_err(), "missing content should produce an error");
+    }
+
+    /// Content exceeding the size limit is rejected.
+    #[tokio::test]
+    async fn test_memory_save_content_size_limit() {
+        let (manager, _tmp) = setup_manager().await;
+        let tool = MemorySaveTool::new(Arc::clone(&manager));
+
+        // 50 KB limit is enforced by MemoryManager's MemoryWriter impl
+        let big = "x".repeat(50 * 1024 + 1);
+        let result = tool.execute(json!({ "content": big })).await;
+        assert!(result.is_err(), "oversized content should be rejected");
+
+        let at_limit = "x".repeat(50 * 1024);
+        let result = tool.execute(json!({ "content": at_limit })).await;
+        assert!(result.is_ok(), "content at limit should succeed");
+    }
+
+    // ---- FetchUrlTool tests ----
+
+    #[test]
+    fn test_fetch_url_tool_schema() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let (manager, _tmp) = rt.block_on(setup_manager());
+        let tool = FetchUrlTool::new(manager);
+        assert_eq!(tool.name(), "fetch_url");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["url"].is_object());
+        assert!(
+            schema["required"]
+                .as_array()
+                .unwrap()
+                .contains(&json!("url"))
+        );
+    }
 
-        let pool = SqlitePool::connect(":memory:").await.unwrap();
-        run_migrations(&pool).await.unwrap();
+    #[test]
+    fn test_is_textual_content_type() {
+        assert!(is_textual_content_type("text/html; charset=utf-8"));
+        assert!(is_textual_content_type("text/plain"));
+        assert!(is_textual_content_type("application/json"));
+        assert!(!is_textual_content_type("image/png"));
+        assert!(!is_textual_content_type("application/octet-stream"));
+        assert!(!is_textual_content_type(""));
+    }
 
-        let config = MemoryConfig {
-            db_path: ":memory:".into(),
-            data_dir: Some(data_dir),
-            memory_dirs: vec![tmp.path().join("MEMORY.md"), mem_dir],
-            chunk_size: 50,
-            chunk_overlap: 10,
-            vector_weight: 0.7,
-            keyword_weight: 0.3,
-            ..Default::default()
-        };
+    /// Ingesting fetched text directly (bypassing the network call) stores
+    /// it as searchable chunks keyed by URL.
+    #[tokio::test]
+    async fn test_ingest_text_makes_content_searchable() {
+        let (manager, _tmp) = setup_manager().await;
+        let ids = manager
+            .ingest_text(
+                "https://example.com/rust",
+                "web_fetch",
+                "Rust is a systems programming language.",
+            )
+            .await
+            .unwrap();
+        assert_eq!(ids.len(), 1);
 
-        let store = Box::new(SqliteMemoryStore::new(pool));
-        let embedder = Box::new(MockEmbedder);
-        let manager = Arc::new(MemoryManager::new(config, store, embedder));
-        (manager, tmp)
+        let search_tool = MemorySearchTool::new(manager);
+        let found = search_tool
+            .execute(json!({ "query": "rust", "limit": 5 }))
+            .await
+            .unwrap();
+        let results = found["results"].as_array().unwrap();
+        assert!(!results.is_empty());
+        assert_eq!(results[0]["path"], json!("https://example.com/rust"));
     }
 
+    // ---- FetchCrateTool tests ----
+
     #[test]
-    fn test_memory_search_tool_schema() {
-        // Schema checks don't need a real manager — use a tokio runtime just to build one
+    fn test_fetch_crate_tool_schema() {
         let rt = tokio::runtime::Runtime::new().unwrap();
         let (manager, _tmp) = rt.block_on(setup_manager());
-        let tool = MemorySearchTool::new(manager);
-        assert_eq!(tool.name(), "memory_search");
+        let tool = FetchCrateTool::new(manager);
+        assert_eq!(tool.name(), "fetch_crate");
         let schema = tool.parameters_schema();
-        assert!(schema["properties"]["query"].is_object());
+        assert!(schema["properties"]["name"].is_object());
+        assert!(schema["properties"]["version"].is_object());
         assert!(
             schema["required"]
                 .as_array()
                 .unwrap()
-                .contains(&json!("query"))
+                .contains(&json!("name"))
         );
     }
 
     #[test]
-    fn test_memory_get_tool_schema() {
+    fn test_is_ingestible_crate_file() {
+        assert!(is_ingestible_crate_file("serde-1.0.0/src/lib.rs"));
+        assert!(is_ingestible_crate_file("serde-1.0.0/Cargo.toml"));
+        assert!(is_ingestible_crate_file("serde-1.0.0/README.md"));
+        assert!(!is_ingestible_crate_file("serde-1.0.0/src/lib.rs.orig"));
+        assert!(!is_ingestible_crate_file("serde-1.0.0/Cargo.lock"));
+        assert!(!is_ingestible_crate_file("serde-1.0.0/benches/data.bin"));
+    }
+
+    /// Ingesting a crate file's text directly (bypassing the crates.io
+    /// fetch) stores it under the namespaced key produced by the tool.
+    #[tokio::test]
+    async fn test_ingest_text_makes_crate_source_searchable() {
+        let (manager, _tmp) = setup_manager().await;
+        let ids = manager
+            .ingest_text(
+                "serde-1.0.0/src/lib.rs",
+                "crate_source",
+                "pub fn serialize() { /* rust serde code */ }",
+            )
+            .await
+            .unwrap();
+        assert_eq!(ids.len(), 1);
+
+        let search_tool = MemorySearchTool::new(manager);
+        let found = search_tool
+            .execute(json!({ "query": "serialize", "limit": 5 }))
+            .await
+            .unwrap();
+        let results = found["results"].as_array().unwrap();
+        assert!(!results.is_empty());
+        assert_eq!(results[0]["path"], json!("serde-1.0.0/src/lib.rs"));
+    }
+
+    // ---- MemoryCrawlTool tests ----
+
+    #[test]
+    fn test_memory_crawl_tool_schema() {
         let rt = tokio::runtime::Runtime::new().unwrap();
         let (manager, _tmp) = rt.block_on(setup_manager());
-        let tool = MemoryGetTool::new(manager);
-        assert_eq!(tool.name(), "memory_get");
+        let tool = MemoryCrawlTool::new(manager);
+        assert_eq!(tool.name(), "memory_crawl");
         let schema = tool.parameters_schema();
-        assert!(schema["properties"]["chunk_id"].is_object());
+        assert!(schema["properties"]["root"].is_object());
         assert!(
             schema["required"]
                 .as_array()
                 .unwrap()
-                .contains(&json!("chunk_id"))
+                .contains(&json!("root"))
         );
     }
 
-    /// Execute memory_search via the tool interface and verify JSON output structure.
     #[tokio::test]
-    async fn test_memory_search_tool_execute() {
+    async fn test_memory_crawl_indexes_matching_files() {
         let (manager, tmp) = setup_manager().await;
-        let mem_dir = tmp.path().join("memory");
-
-        std::fs::write(
-            mem_dir.join("note.md"),
-            "Rust is a systems programming language with great memory safety.",
-        )
-        .unwrap();
-
-        manager.sync().await.unwrap();
+        let docs_dir = tmp.path().join("docs");
+        std::fs::create_dir_all(&docs_dir).unwrap();
+        std::fs::write(docs_dir.join("a.md"), "Notes about rust programming.").unwrap();
+        std::fs::write(docs_dir.join("b.bin"), [0u8, 1, 2, 3]).unwrap();
 
-        let tool = MemorySearchTool::new(manager);
+        let tool = MemoryCrawlTool::new(Arc::clone(&manager));
         let result = tool
-            .execute(json!({ "query": "rust memory", "limit": 3 }))
+            .execute(json!({ "root": "docs", "max_files": 10 }))
             .await
             .unwrap();
 
-        // Verify JSON structure
-        let results = result["results"].as_array().unwrap();
-        assert!(!results.is_empty(), "execute should return results");
+        assert_eq!(result["indexed"], json!(1), "only the .md file should be indexed");
+        assert!(result["chunks"].as_u64().unwrap() >= 1);
 
-        let first = &results[0];
-        assert!(first["chunk_id"].is_string());
-        assert!(first["path"].is_string());
-        assert!(first["score"].is_f64());
-        assert!(first["text"].is_string());
-        assert!(first["start_line"].is_number());
-        assert!(first["end_line"].is_number());
+        let search = MemorySearchTool::new(manager);
+        let found = search
+            .execute(json!({ "query": "rust", "limit": 5 }))
+            .await
+            .unwrap();
+        assert!(!found["results"].as_array().unwrap().is_empty());
+    }
 
-        // The text should contain what we wrote
-        let text = first["text"].as_str().unwrap();
-        assert!(
-            text.contains("Rust"),
-            "search result text should contain 'Rust', got: {text}"
+    #[tokio::test]
+    async fn test_memory_crawl_extension_skip_is_scoped_to_its_root() {
+        let (manager, tmp) = setup_manager().await;
+        let docs_dir = tmp.path().join("docs");
+        let other_dir = tmp.path().join("other");
+        std::fs::create_dir_all(&docs_dir).unwrap();
+        std::fs::create_dir_all(&other_dir).unwrap();
+        std::fs::write(docs_dir.join("a.md"), "Notes about rust programming.").unwrap();
+        std::fs::write(other_dir.join("b.md"), "Notes about a different project.").unwrap();
+
+        let tool = MemoryCrawlTool::new(Arc::clone(&manager));
+        let docs_result = tool.execute(json!({ "root": "docs", "max_files": 10 })).await.unwrap();
+        assert_eq!(docs_result["indexed"], json!(1));
+
+        let other_result = tool.execute(json!({ "root": "other", "max_files": 10 })).await.unwrap();
+        assert_eq!(
+            other_result["indexed"],
+            json!(1),
+            "a completed crawl of docs/ must not cause other/'s .md files to be skipped"
         );
+        assert_eq!(other_result["skipped"], json!(0));
     }
 
-    /// Execute memory_search with missing query — should return an error.
     #[tokio::test]
-    async fn test_memory_search_tool_missing_query() {
+    async fn test_memory_crawl_rejects_traversal() {
         let (manager, _tmp) = setup_manager().await;
-        let tool = MemorySearchTool::new(manager);
-        let result = tool.execute(json!({})).await;
-        assert!(result.is_err(), "missing query should produce an error");
+        let tool = MemoryCrawlTool::new(manager);
+
+        let result = tool.execute(json!({ "root": "../etc" })).await;
+        assert!(result.is_err(), "should reject path traversal");
     }
 
-    /// Execute memory_get for an existing chunk.
+    // ---- ListDirTool / IngestDirTool tests ----
+
     #[tokio::test]
-    async fn test_memory_get_tool_execute() {
+    async fn test_list_dir_reports_entries_with_type_and_trailing_slash() {
         let (manager, tmp) = setup_manager().await;
-        let mem_dir = tmp.path().join("memory");
+        let docs_dir = tmp.path().join("docs");
+        std::fs::create_dir_all(&docs_dir).unwrap();
+        std::fs::write(docs_dir.join("a.md"), "hello").unwrap();
+        std::fs::create_dir_all(docs_dir.join("sub")).unwrap();
+
+        let tool = ListDirTool::new(manager);
+        let result = tool.execute(json!({ "path": "docs" })).await.unwrap();
+
+        let entries = result["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        let file_entry = entries.iter().find(|e| e["name"] == "a.md").unwrap();
+        assert_eq!(file_entry["path_type"], json!("File"));
+        let dir_entry = entries.iter().find(|e| e["name"] == "sub/").unwrap();
+        assert_eq!(dir_entry["path_type"], json!("Dir"));
+    }
 
-        std::fs::write(mem_dir.join("data.md"), "Some database content here.").unwrap();
-        manager.sync().await.unwrap();
+    #[tokio::test]
+    async fn test_list_dir_rejects_traversal() {
+        let (manager, _tmp) = setup_manager().await;
+        let tool = ListDirTool::new(manager);
 
-        // First search to find a chunk_id
+        let result = tool.execute(json!({ "path": "../etc" })).await;
+        assert!(result.is_err(), "should reject path traversal");
+    }
+
+    #[tokio::test]
+    async fn test_ingest_dir_filters_by_extension_and_is_retrievable() {
+        let (manager, tmp) = setup_manager().await;
+        let docs_dir = tmp.path().join("docs");
+        std::fs::create_dir_all(docs_dir.join("sub")).unwrap();
+        std::fs::write(docs_dir.join("a.md"), "Notes about rust programming.").unwrap();
+        std::fs::write(docs_dir.join("b.txt"), "Irrelevant text file.").unwrap();
+        std::fs::write(docs_dir.join("sub").join("c.md"), "More rust notes.").unwrap();
+
+        let tool = IngestDirTool::new(Arc::clone(&manager));
+        let result = tool
+            .execute(json!({ "root": "docs", "extensions": ["md"], "max_depth": 5 }))
+            .await
+            .unwrap();
+
+        assert_eq!(result["indexed"], json!(2), "only the two .md files should be indexed");
+        assert_eq!(result["skipped"], json!(1));
+
+        let search = MemorySearchTool::new(manager);
+        let found = search
+            .execute(json!({ "query": "rust", "limit": 5 }))
+            .await
+            .unwrap();
+        assert!(!found["results"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_ingest_dir_respects_max_depth() {
+        let (manager, tmp) = setup_manager().await;
+        let docs_dir = tmp.path().join("docs");
+        std::fs::create_dir_all(docs_dir.join("sub")).unwrap();
+        std::fs::write(docs_dir.join("sub").join("deep.md"), "Deep notes.").unwrap();
+
+        let tool = IngestDirTool::new(manager);
+        let result = tool
+            .execute(json!({ "root": "docs", "extensions": ["md"], "max_depth": 0 }))
+            .await
+            .unwrap();
+
+        assert_eq!(result["indexed"], json!(0), "depth 0 should not descend into sub/");
+    }
+
+    #[tokio::test]
+    async fn test_ingest_dir_rejects_traversal() {
+        let (manager, _tmp) = setup_manager().await;
+        let tool = IngestDirTool::new(manager);
+
+        let result = tool.execute(json!({ "root": "../etc" })).await;
+        assert!(result.is_err(), "should reject path traversal");
+    }
+
+    /// Full round-trip: save → search → get → verify text matches.
+    #[tokio::test]
+    async fn test_memory_save_round_trip() {
+        let (manager, _tmp) = setup_manager().await;
+        let save_tool = MemorySaveTool::new(Arc::clone(&manager));
         let search_tool = MemorySearchTool::new(Arc::clone(&manager));
+        let get_tool = MemoryGetTool::new(Arc::clone(&manager));
+
+        let text = "Music from the jazz era is deeply expressive and soulful.";
+        save_tool
+            .execute(json!({ "content": text, "file": "memory/jazz.md" }))
+            .await
+            .unwrap();
+
+        // Search
         let search_result = search_tool
-            .execute(json!({ "query": "database", "limit": 1 }))
+            .execute(json!({ "query": "music", "limit": 1 }))
             .await
             .unwrap();
-        let chunk_id = search_result["results"][0]["chunk_id"]
-            .as_str()
-            .unwrap()
-            .to_string();
+        let results = search_result["results"].as_array().unwrap();
+        assert!(!results.is_empty(), "saved content should be searchable");
+        let chunk_id = results[0]["chunk_id"].as_str().unwrap();
 
-        // Now get that chunk
-        let get_tool = MemoryGetTool::new(manager);
-        let result = get_tool
+        // Get
+        let get_result = get_tool
             .execute(json!({ "chunk_id": chunk_id }))
             .await
             .unwrap();
+        let retrieved = get_result["text"].as_str().unwrap();
+        assert!(
+            retrieved.contains("jazz era"),
+            "round-trip text should contain saved content, got: {retrieved}"
+        );
+    }
 
-        assert!(result["error"].is_null(), "should not have error");
-        assert_eq!(result["chunk_id"].as_str().unwrap(), chunk_id);
-        let text = result["text"].as_str().unwrap();
+    // ---- MemoryReindexTool tests ----
+
+    #[test]
+    fn test_memory_reindex_tool_schema() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let (manager, _tmp) = rt.block_on(setup_manager());
+        let tool = MemoryReindexTool::new(manager);
+        assert_eq!(tool.name(), "memory_reindex");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["path"].is_object());
         assert!(
-            text.contains("database"),
-            "retrieved chunk should contain 'database', got: {text}"
+            schema["required"]
+                .as_array()
+                .unwrap()
+                .contains(&json!("path"))
         );
     }
 
-    /// Execute memory_get for a non-existent chunk — should return error JSON (not a Rust error).
+    /// Reindexing after an out-of-band file change picks up the new content.
     #[tokio::test]
-    async fn test_memory_get_tool_not_found() {
-        let (manager, _tmp) = setup_manager().await;
-        let tool = MemoryGetTool::new(manager);
+    async fn test_memory_reindex_picks_up_changes() {
+        let (manager, tmp) = setup_manager().await;
+        let mem_dir = tmp.path().join("memory");
+        std::fs::write(mem_dir.join("note.md"), "Original content about rust.").unwrap();
+        manager.sync().await.unwrap();
+
+        std::fs::write(mem_dir.join("note.md"), "Updated content about database.").unwrap();
+
+        let tool = MemoryReindexTool::new(Arc::clone(&manager));
         let result = tool
-            .execute(json!({ "chunk_id": "nonexistent-id" }))
+            .execute(json!({ "path": "memory/note.md" }))
             .await
             .unwrap();
+        assert_eq!(result["chunks"], json!(1));
 
-        assert_eq!(result["error"].as_str().unwrap(), "chunk not found");
-        assert_eq!(result["chunk_id"].as_str().unwrap(), "nonexistent-id");
+        let search_tool = MemorySearchTool::new(manager);
+        let found = search_tool
+            .execute(json!({ "query": "database", "limit": 5 }))
+            .await
+            .unwrap();
+        assert!(!found["results"].as_array().unwrap().is_empty());
     }
 
-    /// Execute memory_get with missing chunk_id — should return an error.
+    /// Reindexing a path that no longer exists clears its chunks.
     #[tokio::test]
-    async fn test_memory_get_tool_missing_param() {
+    async fn test_memory_reindex_clears_deleted_file() {
+        let (manager, tmp) = setup_manager().await;
+        let mem_dir = tmp.path().join("memory");
+        std::fs::write(mem_dir.join("gone.md"), "Content about cooking.").unwrap();
+        manager.sync().await.unwrap();
+
+        std::fs::remove_file(mem_dir.join("gone.md")).unwrap();
+
+        let tool = MemoryReindexTool::new(Arc::clone(&manager));
+        let result = tool
+            .execute(json!({ "path": "memory/gone.md" }))
+            .await
+            .unwrap();
+        assert_eq!(result["chunks"], json!(0));
+
+        let search_tool = MemorySearchTool::new(manager);
+        let found = search_tool
+            .execute(json!({ "query": "cooking", "limit": 5 }))
+            .await
+            .unwrap();
+        assert!(found["results"].as_array().unwrap().is_empty());
+    }
+
+    /// Path traversal attempts are rejected.
+    #[tokio::test]
+    async fn test_memory_reindex_rejects_traversal() {
         let (manager, _tmp) = setup_manager().await;
-        let tool = MemoryGetTool::new(manager);
-        let result = tool.execute(json!({})).await;
-        assert!(result.is_err(), "missing chunk_id should produce an error");
+        let tool = MemoryReindexTool::new(manager);
+
+        let result = tool.execute(json!({ "path": "../etc/passwd" })).await;
+        assert!(result.is_err(), "should reject path traversal");
     }
 
-    /// Round-trip: sync → search via tool → get via tool → verify text matches.
+    // ---- MemoryDeleteTool tests ----
+
+    #[test]
+    fn test_memory_delete_tool_schema() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let (manager, _tmp) = rt.block_on(setup_manager());
+        let tool = MemoryDeleteTool::new(manager);
+        assert_eq!(tool.name(), "memory_delete");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["chunk_id"].is_object());
+        assert!(
+            schema["required"]
+                .as_array()
+                .unwrap()
+                .contains(&json!("chunk_id"))
+        );
+    }
+
+    /// Deleting just the chunk leaves the file and its other chunks intact.
     #[tokio::test]
-    async fn test_tools_round_trip() {
+    async fn test_memory_delete_chunk_only() {
         let (manager, tmp) = setup_manager().await;
         let mem_dir = tmp.path().join("memory");
+        std::fs::write(mem_dir.join("notes.md"), "Rust notes about memory safety.").unwrap();
+        manager.sync().await.unwrap();
 
-        let original_text = "Cooking pasta with fresh herbs and olive oil is a delight.";
-        std::fs::write(mem_dir.join("recipe.md"), original_text).unwrap();
+        let search_tool = MemorySearchTool::new(Arc::clone(&manager));
+        let found = search_tool
+            .execute(json!({ "query": "rust", "limit": 1 }))
+            .await
+            .unwrap();
+        let chunk_id = found["results"][0]["chunk_id"].as_str().unwrap().to_string();
+
+        let tool = MemoryDeleteTool::new(Arc::clone(&manager));
+        let result = tool.execute(json!({ "chunk_id": chunk_id })).await.unwrap();
+        assert_eq!(result["found"], json!(true));
+        assert_eq!(result["file_deleted"], json!(false));
+
+        assert!(mem_dir.join("notes.md").exists(), "file should remain");
+        assert!(
+            manager.get_chunk(&chunk_id).await.unwrap().is_none(),
+            "chunk should be gone from the store"
+        );
+    }
+
+    /// Deleting with delete_file removes the file and all of its chunks.
+    #[tokio::test]
+    async fn test_memory_delete_whole_file() {
+        let (manager, tmp) = setup_manager().await;
+        let mem_dir = tmp.path().join("memory");
+        std::fs::write(mem_dir.join("stale.md"), "Old content about cooking.").unwrap();
         manager.sync().await.unwrap();
 
         let search_tool = MemorySearchTool::new(Arc::clone(&manager));
-        let get_tool = MemoryGetTool::new(Arc::clone(&manager));
-
-        // Search
-        let search_result = search_tool
+        let found = search_tool
             .execute(json!({ "query": "cooking", "limit": 1 }))
             .await
             .unwrap();
-        let results = search_result["results"].as_array().unwrap();
-        assert_eq!(results.len(), 1);
-        let chunk_id = results[0]["chunk_id"].as_str().unwrap();
+        let chunk_id = found["results"][0]["chunk_id"].as_str().unwrap().to_string();
 
-        // Get
-        let get_result = get_tool
-            .execute(json!({ "chunk_id": chunk_id }))
+        let tool = MemoryDeleteTool::new(Arc::clone(&manager));
+        let result = tool
+            .execute(json!({ "chunk_id": chunk_id, "delete_file": true }))
             .await
             .unwrap();
-        let retrieved_text = get_result["text"].as_str().unwrap();
+        assert_eq!(result["found"], json!(true));
+        assert_eq!(result["file_deleted"], json!(true));
 
-        assert_eq!(
-            retrieved_text, original_text,
-            "round-trip text should match original"
+        assert!(!mem_dir.join("stale.md").exists(), "file should be deleted");
+        let remaining = search_tool
+            .execute(json!({ "query": "cooking", "limit": 5 }))
+            .await
+            .unwrap();
+        assert!(remaining["results"].as_array().unwrap().is_empty());
+    }
+
+    /// Unknown chunk_id returns found: false rather than an error.
+    #[tokio::test]
+    async fn test_memory_delete_not_found() {
+        let (manager, _tmp) = setup_manager().await;
+        let tool = MemoryDeleteTool::new(manager);
+        let result = tool
+            .execute(json!({ "chunk_id": "nonexistent" }))
+            .await
+            .unwrap();
+        assert_eq!(result["found"], json!(false));
+    }
+
+    /// `delete_file: true` on a chunk with no on-disk file (e.g. one from
+    /// `fetch_url`, keyed by URL rather than a `data_dir`-relative path) is
+    /// rejected up front instead of erroring confusingly on path validation.
+    #[tokio::test]
+    async fn test_memory_delete_file_rejected_for_non_file_backed_source() {
+        let (manager, _tmp) = setup_manager().await;
+        let ids = manager
+            .ingest_text("https://example.com/post", "web_fetch", "Some fetched article text.")
+            .await
+            .unwrap();
+        let chunk_id = &ids[0];
+
+        let tool = MemoryDeleteTool::new(Arc::clone(&manager));
+        let result = tool
+            .execute(json!({ "chunk_id": chunk_id, "delete_file": true }))
+            .await;
+        assert!(result.is_err(), "should reject delete_file for a web_fetch chunk");
+
+        assert!(
+            manager.get_chunk(chunk_id).await.unwrap().is_some(),
+            "chunk should be untouched after the rejected delete"
         );
     }
 
-    // ---- MemorySaveTool tests ----
+    // ---- MemoryEditTool tests ----
 
     #[test]
-    fn test_memory_save_tool_schema() {
+    fn test_memory_edit_tool_schema() {
         let rt = tokio::runtime::Runtime::new().unwrap();
         let (manager, _tmp) = rt.block_on(setup_manager());
-        let tool = MemorySaveTool::new(manager);
-        assert_eq!(tool.name(), "memory_save");
+        let tool = MemoryEditTool::new(manager);
+        assert_eq!(tool.name(), "memory_edit");
         let schema = tool.parameters_schema();
-        assert!(schema["properties"]["content"].is_object());
         assert!(schema["properties"]["file"].is_object());
-        assert!(schema["properties"]["append"].is_object());
         assert!(
             schema["required"]
                 .as_array()
                 .unwrap()
-                .contains(&json!("content"))
+                .contains(&json!("file"))
         );
     }
 
-    /// Default append mode: two writes produce both contents in the file.
+    /// Deletes, replaces, and adds are applied in that order in a single call.
     #[tokio::test]
-    async fn test_memory_save_append_default() {
+    async fn test_memory_edit_applies_in_order() {
         let (manager, tmp) = setup_manager().await;
         let data_dir = tmp.path().to_path_buf();
-        let tool = MemorySaveTool::new(Arc::clone(&manager));
+        std::fs::write(
+            data_dir.join("MEMORY.md"),
+            "Stale note.\nRust is great.\nPython is fine.",
+        )
+        .unwrap();
 
-        let r1 = tool
-            .execute(json!({ "content": "First memory about rust." }))
+        let tool = MemoryEditTool::new(Arc::clone(&manager));
+        let result = tool
+            .execute(json!({
+                "file": "MEMORY.md",
+                "deletes": ["Stale note.\n"],
+                "replaces": [{ "match": "Python is fine.", "replacement": "Python is great too." }],
+                "adds": ["Go is solid as well."]
+            }))
             .await
             .unwrap();
-        assert_eq!(r1["saved"], json!(true));
-        assert_eq!(r1["path"], json!("MEMORY.md"));
 
-        let r2 = tool
-            .execute(json!({ "content": "Second memory about database." }))
-            .await
-            .unwrap();
-        assert_eq!(r2["saved"], json!(true));
+        assert_eq!(result["chunks"].as_u64().unwrap(), 1);
 
         let content = std::fs::read_to_string(data_dir.join("MEMORY.md")).unwrap();
-        assert!(content.contains("First memory"), "should have first write");
-        assert!(
-            content.contains("Second memory"),
-            "should have second write"
-        );
+        assert!(!content.contains("Stale note"));
+        assert!(content.contains("Python is great too."));
+        assert!(content.contains("Go is solid as well."));
     }
 
-    /// Overwrite mode: second write replaces the first.
+    /// The edited file is immediately searchable for its new content.
     #[tokio::test]
-    async fn test_memory_save_overwrite() {
+    async fn test_memory_edit_reindexes() {
         let (manager, tmp) = setup_manager().await;
         let data_dir = tmp.path().to_path_buf();
-        let tool = MemorySaveTool::new(Arc::clone(&manager));
-
-        tool.execute(json!({ "content": "Original content about rust." }))
-            .await
-            .unwrap();
+        std::fs::write(data_dir.join("MEMORY.md"), "Notes about databases.").unwrap();
 
+        let tool = MemoryEditTool::new(Arc::clone(&manager));
         tool.execute(json!({
-            "content": "Replaced content about database.",
-            "append": false
+            "file": "MEMORY.md",
+            "adds": ["New info about networking."]
         }))
         .await
         .unwrap();
 
-        let content = std::fs::read_to_string(data_dir.join("MEMORY.md")).unwrap();
+        let search_tool = MemorySearchTool::new(manager);
+        let found = search_tool
+            .execute(json!({ "query": "networking", "limit": 5 }))
+            .await
+            .unwrap();
+        assert!(!found["results"].as_array().unwrap().is_empty());
+    }
+
+    /// Invalid file names are rejected, same as MemorySaveTool.
+    #[tokio::test]
+    async fn test_memory_edit_rejects_invalid_path() {
+        let (manager, _tmp) = setup_manager().await;
+        let tool = MemoryEditTool::new(manager);
+        let result = tool
+            .execute(json!({ "file": "../etc/passwd", "adds": ["x"] }))
+            .await;
+        assert!(result.is_err(), "should reject path traversal");
+    }
+
+    // ---- MemoryAnswerTool tests ----
+
+    #[test]
+    fn test_memory_answer_tool_schema() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let (manager, _tmp) = rt.block_on(setup_manager());
+        let tool = MemoryAnswerTool::new(manager);
+        assert_eq!(tool.name(), "memory_answer");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["query"].is_object());
         assert!(
-            !content.contains("Original"),
-            "overwrite should remove original"
+            schema["required"]
+                .as_array()
+                .unwrap()
+                .contains(&json!("query"))
         );
-        assert!(content.contains("Replaced"), "overwrite should have new");
     }
 
-    /// Custom file under memory/ subdirectory.
+    /// Assembles a context string with sources from matching memory content.
     #[tokio::test]
-    async fn test_memory_save_custom_file() {
+    async fn test_memory_answer_assembles_context() {
         let (manager, tmp) = setup_manager().await;
-        let data_dir = tmp.path().to_path_buf();
-        let tool = MemorySaveTool::new(Arc::clone(&manager));
+        let mem_dir = tmp.path().join("memory");
+        std::fs::write(mem_dir.join("a.md"), "Rust has great memory safety.").unwrap();
+        std::fs::write(mem_dir.join("b.md"), "Python is a dynamic language.").unwrap();
+        manager.sync().await.unwrap();
 
+        let tool = MemoryAnswerTool::new(manager);
         let result = tool
-            .execute(json!({
-                "content": "Notes from 2024-01-15 about cooking.",
-                "file": "memory/2024-01-15.md"
-            }))
+            .execute(json!({ "query": "rust memory", "limit": 5 }))
             .await
             .unwrap();
 
-        assert_eq!(result["saved"], json!(true));
-        assert_eq!(result["path"], json!("memory/2024-01-15.md"));
+        let context = result["context"].as_str().unwrap();
+        assert!(context.contains("Rust"), "context should include matching text");
 
-        let content =
-            std::fs::read_to_string(data_dir.join("memory").join("2024-01-15.md")).unwrap();
-        assert!(content.contains("Notes from 2024-01-15"));
+        let sources = result["sources"].as_array().unwrap();
+        assert!(!sources.is_empty());
+        assert!(sources[0]["path"].is_string());
+        assert!(sources[0]["citation"].is_string());
     }
 
-    /// Auto-creates memory/ directory if it doesn't exist.
+    /// A tiny max_context_chars budget still returns at least the top passage.
     #[tokio::test]
-    async fn test_memory_save_creates_memory_dir() {
+    async fn test_memory_answer_respects_budget() {
         let (manager, tmp) = setup_manager().await;
-        let data_dir = tmp.path().to_path_buf();
-        // Remove the memory dir that setup_manager created
-        std::fs::remove_dir_all(data_dir.join("memory")).unwrap();
-        assert!(!data_dir.join("memory").exists());
-
-        let tool = MemorySaveTool::new(Arc::clone(&manager));
-        tool.execute(json!({
-            "content": "Content for new dir.",
-            "file": "memory/notes.md"
-        }))
-        .await
+        let mem_dir = tmp.path().join("memory");
+        std::fs::write(
+            mem_dir.join("notes.md"),
+            "Rust memory safety notes.\n\n\n\n\n\n\n\n\n\nPython dynamic typing notes.",
+        )
         .unwrap();
+        manager.sync().await.unwrap();
 
-        assert!(data_dir.join("memory").join("notes.md").exists());
-    }
-
-    /// Re-indexes after write so content is immediately searchable.
-    #[tokio::test]
-    async fn test_memory_save_reindexes() {
-        let (manager, _tmp) = setup_manager().await;
-        let save_tool = MemorySaveTool::new(Arc::clone(&manager));
-        let search_tool = MemorySearchTool::new(Arc::clone(&manager));
-
-        save_tool
-            .execute(json!({
-                "content": "The cooking recipe uses garlic and olive oil.",
-                "file": "memory/recipe.md"
-            }))
+        let tool = MemoryAnswerTool::new(Arc::clone(&manager));
+        let full = tool
+            .execute(json!({ "query": "rust python", "limit": 5, "max_context_chars": 100_000 }))
             .await
             .unwrap();
-
-        let results = search_tool
-            .execute(json!({ "query": "cooking", "limit": 5 }))
+        let tight = tool
+            .execute(json!({ "query": "rust python", "limit": 5, "max_context_chars": 1 }))
             .await
             .unwrap();
 
-        let items = results["results"].as_array().unwrap();
-        assert!(!items.is_empty(), "saved content should be searchable");
-        assert!(
-            items[0]["text"].as_str().unwrap().contains("cooking"),
-            "search should find the saved text"
-        );
+        let full_sources = full["sources"].as_array().unwrap().len();
+        let tight_sources = tight["sources"].as_array().unwrap().len();
+        assert!(tight_sources <= full_sources);
+        assert!(tight_sources >= 1, "should always include at least one passage");
     }
 
-    /// Path traversal attempts are rejected.
+    /// Missing query returns an error.
     #[tokio::test]
-    async fn test_memory_save_rejects_path_traversal() {
+    async fn test_memory_answer_missing_query() {
         let (manager, _tmp) = setup_manager().await;
-        let tool = MemorySaveTool::new(Arc::clone(&manager));
-
-        for bad_path in &[
-            "../etc/passwd",
-            "memory/../../../etc/passwd",
-            "memory/../../secret.md",
-        ] {
-            let result = tool
-                .execute(json!({ "content": "test", "file": bad_path }))
-                .await;
-            assert!(result.is_err(), "should reject path traversal: {bad_path}");
-        }
+        let tool = MemoryAnswerTool::new(manager);
+        let result = tool.execute(json!({})).await;
+        assert!(result.is_err(), "missing query should produce an error");
     }
 
-    /// Absolute paths are rejected.
-    #[tokio::test]
-    async fn test_memory_save_rejects_absolute_paths() {
-        let (manager, _tmp) = setup_manager().await;
-        let tool = MemorySaveTool::new(Arc::clone(&manager));
-
-        let result = tool
-            .execute(json!({ "content": "test", "file": "/etc/passwd" }))
-            .await;
-        assert!(result.is_err(), "should reject absolute paths");
-    }
+    // ---- VerifyStoreTool tests ----
 
-    /// Invalid file names are rejected.
     #[tokio::test]
-    async fn test_memory_save_rejects_invalid_names() {
+    async fn test_verify_store_reports_clean_after_save() {
         let (manager, _tmp) = setup_manager().await;
-        let tool = MemorySaveTool::new(Arc::clone(&manager));
+        let save_tool = MemorySaveTool::new(Arc::clone(&manager));
+        save_tool
+            .execute(json!({ "content": "Some notes.", "file": "memory/notes.md" }))
+            .await
+            .unwrap();
 
-        let invalid = &[
-            "memory/notes.txt",     // wrong extension
-            "memory/.md",           // empty stem
-            "memory/a b c.md",      // spaces in name
-            "memory/sub/nested.md", // nested subdirectory
-            "random.md",            // not MEMORY.md or memory/
-            "foo/bar.md",           // not in allowed paths
-        ];
+        let tool = VerifyStoreTool::new(manager);
+        let result = tool.execute(json!({})).await.unwrap();
 
-        for name in invalid {
-            let result = tool
-                .execute(json!({ "content": "test", "file": name }))
-                .await;
-            assert!(result.is_err(), "should reject invalid name: {name}");
-        }
+        assert_eq!(result["clean"], json!(true));
+        assert!(result["checked"].as_u64().unwrap() >= 1);
+        assert!(result["tampered"].as_array().unwrap().is_empty());
     }
 
-    /// Missing content parameter returns an error.
     #[tokio::test]
-    async fn test_memory_save_missing_content() {
+    async fn test_verify_store_detects_tampered_chunk() {
         let (manager, _tmp) = setup_manager().await;
-        let tool = MemorySaveTool::new(Arc::clone(&manager));
+        let save_tool = MemorySaveTool::new(Arc::clone(&manager));
+        save_tool
+            .execute(json!({ "content": "Original content.", "file": "memory/notes.md" }))
+            .await
+            .unwrap();
 
-        let result = tool.execute(json!({})).await;
-        assert!(result.is_err(), "missing content should produce an error");
-    }
+        let search = MemorySearchTool::new(Arc::clone(&manager));
+        let found = search.execute(json!({ "query": "original" })).await.unwrap();
+        let chunk_id = found["results"][0]["chunk_id"].as_str().unwrap().to_string();
 
-    /// Content exceeding the size limit is rejected.
-    #[tokio::test]
-    async fn test_memory_save_content_size_limit() {
-        let (manager, _tmp) = setup_manager().await;
-        let tool = MemorySaveTool::new(Arc::clone(&manager));
+        // Tamper with the stored text directly through the store, bypassing
+        // the manager so its manifest doesn't follow the change.
+        let mut chunk = manager.get_chunk(&chunk_id).await.unwrap().unwrap();
+        chunk.text = "Tampered content.".into();
+        manager.store().insert_chunk(&chunk).await.unwrap();
 
-        // 50 KB limit is enforced by MemoryManager's MemoryWriter impl
-        let big = "x".repeat(50 * 1024 + 1);
-        let result = tool.execute(json!({ "content": big })).await;
-        assert!(result.is_err(), "oversized content should be rejected");
+        let tool = VerifyStoreTool::new(Arc::clone(&manager));
+        let result = tool.execute(json!({})).await.unwrap();
 
-        let at_limit = "x".repeat(50 * 1024);
-        let result = tool.execute(json!({ "content": at_limit })).await;
-        assert!(result.is_ok(), "content at limit should succeed");
+        assert_eq!(result["clean"], json!(false));
+        assert_eq!(
+            result["tampered"].as_array().unwrap(),
+            &vec![json!(chunk_id.clone())]
+        );
     }
 
-    /// Full round-trip: save → search → get → verify text matches.
     #[tokio::test]
-    async fn test_memory_save_round_trip() {
+    async fn test_get_chunk_rejects_tampered_content() {
         let (manager, _tmp) = setup_manager().await;
         let save_tool = MemorySaveTool::new(Arc::clone(&manager));
-        let search_tool = MemorySearchTool::new(Arc::clone(&manager));
-        let get_tool = MemoryGetTool::new(Arc::clone(&manager));
-
-        let text = "Music from the jazz era is deeply expressive and soulful.";
         save_tool
-            .execute(json!({ "content": text, "file": "memory/jazz.md" }))
+            .execute(json!({ "content": "Original content.", "file": "memory/notes.md" }))
             .await
             .unwrap();
 
-        // Search
-        let search_result = search_tool
-            .execute(json!({ "query": "music", "limit": 1 }))
-            .await
-            .unwrap();
-        let results = search_result["results"].as_array().unwrap();
-        assert!(!results.is_empty(), "saved content should be searchable");
-        let chunk_id = results[0]["chunk_id"].as_str().unwrap();
+        let search = MemorySearchTool::new(Arc::clone(&manager));
+        let found = search.execute(json!({ "query": "original" })).await.unwrap();
+        let chunk_id = found["results"][0]["chunk_id"].as_str().unwrap().to_string();
 
-        // Get
-        let get_result = get_tool
-            .execute(json!({ "chunk_id": chunk_id }))
-            .await
-            .unwrap();
-        let retrieved = get_result["text"].as_str().unwrap();
-        assert!(
-            retrieved.contains("jazz era"),
-            "round-trip text should contain saved content, got: {retrieved}"
-        );
+        let mut chunk = manager.get_chunk(&chunk_id).await.unwrap().unwrap();
+        chunk.text = "Tampered content.".into();
+        manager.store().insert_chunk(&chunk).await.unwrap();
+
+        let get_tool = MemoryGetTool::new(manager);
+        let result = get_tool.execute(json!({ "chunk_id": chunk_id })).await;
+        assert!(result.is_err(), "reading tampered content should fail closed");
     }
 }
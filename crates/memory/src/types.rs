@@ -0,0 +1,21 @@
+//! Shared memory chunk data types.
+
+use serde::{Deserialize, Serialize};
+
+/// A chunk of text indexed from a memory source (a daily log or a long-term
+/// memory file), along with its embedding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryChunk {
+    pub id: String,
+    /// For file-backed chunks, the file's path relative to `data_dir`; for
+    /// fetched content, the key it was ingested under (a URL, or
+    /// `<name>-<version>/<path>` for crate source).
+    pub path: String,
+    /// Logical origin of the content, e.g. `"memory_file"`, `"file_ingest"`,
+    /// `"web_fetch"` or `"crate_source"`.
+    pub source: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub text: String,
+    pub embedding: Vec<f32>,
+}
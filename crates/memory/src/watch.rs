@@ -0,0 +1,213 @@
+//! Long-running filesystem watch mode for [`crate::manager::MemoryManager`].
+//!
+//! Incrementally re-chunks and re-embeds only the files that actually
+//! changed, instead of requiring a full `sync()`. Rapid bursts of
+//! create/modify events for the same path (e.g. an editor's save storm) are
+//! debounced into a single re-index; renames delete the stale chunks under
+//! the old path and re-index under the new one so nothing orphaned lingers
+//! in the store.
+
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use notify::{
+    event::{ModifyKind, RenameMode},
+    Event, EventKind, RecursiveMode, Watcher,
+};
+use tracing::warn;
+
+use crate::manager::MemoryManager;
+
+/// How long a path must go quiet before a debounced create/modify is flushed.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Handle to a running watch task, returned by [`watch`]. Dropping it without
+/// calling [`Self::shutdown`] also stops the watch, since the stop channel
+/// closes, but does not wait for the background task to exit.
+pub struct WatchHandle {
+    stop: tokio::sync::oneshot::Sender<()>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl WatchHandle {
+    /// Stop watching and wait for the background task to exit.
+    pub async fn shutdown(self) {
+        let _ = self.stop.send(());
+        let _ = self.task.await;
+    }
+}
+
+/// A change observed by the OS watcher, after collapsing the raw
+/// [`notify::Event`] down to what the manager needs to act on.
+enum Change {
+    /// A file was created or its contents changed; debounced before acting.
+    Upsert(PathBuf),
+    /// A file was removed.
+    Removed(PathBuf),
+    /// A file moved from one path to another.
+    Renamed { from: PathBuf, to: PathBuf },
+}
+
+fn classify(event: Event) -> Option<Change> {
+    match event.kind {
+        EventKind::Remove(_) => event.paths.into_iter().next().map(Change::Removed),
+        // On backends (e.g. inotify) that can't reliably pair a rename's two
+        // halves into one event, `RenameMode::From`/`To` arrive as separate
+        // single-path events instead of the combined two-path case below.
+        // An unpaired `From` must still be treated as a removal of the old
+        // path — falling through to the generic `Modify(_) => Upsert` arm
+        // would re-index a path that no longer exists and leave its stale
+        // chunks behind — and an unpaired `To` as an upsert of the new one.
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+            event.paths.into_iter().next().map(Change::Removed)
+        }
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+            event.paths.into_iter().next().map(Change::Upsert)
+        }
+        EventKind::Modify(ModifyKind::Name(_)) if event.paths.len() == 2 => {
+            let mut paths = event.paths.into_iter();
+            Some(Change::Renamed {
+                from: paths.next().unwrap(),
+                to: paths.next().unwrap(),
+            })
+        }
+        EventKind::Create(_) | EventKind::Modify(_) => {
+            event.paths.into_iter().next().map(Change::Upsert)
+        }
+        _ => None,
+    }
+}
+
+/// Start watching every path in `manager`'s `memory_dirs` for changes,
+/// incrementally re-indexing as they occur. Returns a handle that must be
+/// kept alive (and can be used to shut the watch down); dropping the
+/// returned [`WatchHandle`] ends the watch task, and with it the
+/// [`notify::RecommendedWatcher`] it owns.
+pub fn watch(manager: Arc<MemoryManager>) -> anyhow::Result<WatchHandle> {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Event>();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+        Ok(event) => {
+            let _ = tx.send(event);
+        }
+        Err(e) => warn!(error = %e, "memory watcher error"),
+    })?;
+
+    for dir in manager.memory_dirs() {
+        watcher.watch(dir, RecursiveMode::Recursive)?;
+    }
+
+    let (stop_tx, mut stop_rx) = tokio::sync::oneshot::channel();
+    let task = tokio::spawn(async move {
+        // Keep the watcher alive for as long as this task runs.
+        let _watcher = watcher;
+        let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+        let mut tick = tokio::time::interval(Duration::from_millis(100));
+
+        loop {
+            tokio::select! {
+                _ = &mut stop_rx => break,
+                event = rx.recv() => {
+                    match event.and_then(classify) {
+                        Some(Change::Upsert(path)) => {
+                            pending.insert(path, Instant::now());
+                        }
+                        Some(Change::Removed(path)) => {
+                            pending.remove(&path);
+                            if let Err(e) = manager.remove_file(&path).await {
+                                warn!(error = %e, path = %path.display(), "failed to remove stale chunks");
+                            }
+                        }
+                        Some(Change::Renamed { from, to }) => {
+                            pending.remove(&from);
+                            if let Err(e) = manager.remove_file(&from).await {
+                                warn!(error = %e, path = %from.display(), "failed to remove chunks for renamed-from path");
+                            }
+                            // Re-indexed immediately rather than debounced: a rename
+                            // is a single atomic event, not a burst.
+                            if let Err(e) = manager.index_file(&to).await {
+                                warn!(error = %e, path = %to.display(), "failed to index renamed-to path");
+                            }
+                        }
+                        None => {}
+                    }
+                }
+                _ = tick.tick() => {
+                    let ready: Vec<PathBuf> = pending
+                        .iter()
+                        .filter(|(_, seen)| seen.elapsed() >= DEBOUNCE)
+                        .map(|(path, _)| path.clone())
+                        .collect();
+                    for path in ready {
+                        pending.remove(&path);
+                        if let Err(e) = manager.index_file(&path).await {
+                            warn!(error = %e, path = %path.display(), "failed to index changed path");
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(WatchHandle { stop: stop_tx, task })
+}
+
+#[cfg(test)]
+mod tests {
+    use notify::event::{CreateKind, RemoveKind};
+
+    use super::*;
+
+    fn event(kind: EventKind, paths: Vec<PathBuf>) -> Event {
+        Event { paths, ..Event::new(kind) }
+    }
+
+    #[test]
+    fn test_classify_remove() {
+        let change = classify(event(EventKind::Remove(RemoveKind::File), vec![PathBuf::from("a.md")]));
+        assert!(matches!(change, Some(Change::Removed(p)) if p == PathBuf::from("a.md")));
+    }
+
+    #[test]
+    fn test_classify_create_is_upsert() {
+        let change = classify(event(EventKind::Create(CreateKind::File), vec![PathBuf::from("a.md")]));
+        assert!(matches!(change, Some(Change::Upsert(p)) if p == PathBuf::from("a.md")));
+    }
+
+    #[test]
+    fn test_classify_paired_rename() {
+        let change = classify(event(
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)),
+            vec![PathBuf::from("old.md"), PathBuf::from("new.md")],
+        ));
+        assert!(matches!(
+            change,
+            Some(Change::Renamed { from, to })
+                if from == PathBuf::from("old.md") && to == PathBuf::from("new.md")
+        ));
+    }
+
+    /// inotify's unpaired halves of a rename: a lone `From` must still be
+    /// treated as a removal of the old path, not fall through to `Upsert`
+    /// and re-index a path that no longer exists.
+    #[test]
+    fn test_classify_unpaired_rename_from_is_removal() {
+        let change = classify(event(
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)),
+            vec![PathBuf::from("old.md")],
+        ));
+        assert!(matches!(change, Some(Change::Removed(p)) if p == PathBuf::from("old.md")));
+    }
+
+    #[test]
+    fn test_classify_unpaired_rename_to_is_upsert() {
+        let change = classify(event(
+            EventKind::Modify(ModifyKind::Name(RenameMode::To)),
+            vec![PathBuf::from("new.md")],
+        ));
+        assert!(matches!(change, Some(Change::Upsert(p)) if p == PathBuf::from("new.md")));
+    }
+}